@@ -19,6 +19,10 @@ struct TagStructureAttributes {
 #[deluxe(attributes(data))]
 struct TagStructureFieldAttributes {
     offset: u64,
+    #[deluxe(default)]
+    block: bool,
+    #[deluxe(default)]
+    min_version: Option<i32>,
 }
 
 fn extract_struct_field_attributes(
@@ -35,6 +39,123 @@ fn extract_struct_field_attributes(
     Ok(field_attributes)
 }
 
+/// Checks if the last segment of a type path is equal to `name`.
+fn is_type_named(ty: &syn::Type, name: &str) -> bool {
+    matches!(ty, syn::Type::Path(type_path)
+        if type_path.path.segments.last().is_some_and(|segment| segment.ident == name))
+}
+
+/// Fields are only detected as blocks by their type name, so aliased or wrapped blocks need to be
+/// marked explicitly using `#[data(block)]`.
+fn is_block_field(
+    field: &syn::Field,
+    field_attributes: &HashMap<String, TagStructureFieldAttributes>,
+) -> bool {
+    let attributes = field_attributes
+        .get(&field.ident.as_ref().unwrap().to_string())
+        .unwrap();
+    attributes.block || is_type_named(&field.ty, "FieldBlock")
+}
+
+/// Generates the statements reading every field of a struct at its offset.
+///
+/// Fields that only exist in newer module versions are wrapped so that they keep their default value when `version`
+/// is older.
+fn field_reads(
+    data: &syn::DataStruct,
+    field_attributes: &HashMap<String, TagStructureFieldAttributes>,
+) -> Vec<proc_macro2::TokenStream> {
+    data.fields.iter().map(|field| {
+        let field_name = &field.ident;
+        let attributes = field_attributes
+            .get(&field_name.as_ref().unwrap().to_string())
+            .unwrap();
+        let offset = attributes.offset;
+        let field_name_str = field_name.as_ref().unwrap().to_string();
+        let read = quote! {
+            reader.seek(std::io::SeekFrom::Start(main_offset + #offset))?;
+            self.#field_name.read(reader).map_err(|error| {
+                infinite_rs::Error::TagError(infinite_rs::common::errors::TagError::FieldReadError {
+                    field: #field_name_str,
+                    offset: main_offset + #offset,
+                    source: Box::new(error),
+                })
+            })?;
+        };
+        match attributes.min_version {
+            Some(min_version) => quote! {
+                if !matches!(version, Some(version) if (version.clone() as i32) < #min_version) {
+                    #read
+                }
+            },
+            None => read,
+        }
+    }).collect()
+}
+
+/// Generates the statements loading the elements of every block field of a struct.
+fn field_blocks(
+    data: &syn::DataStruct,
+    field_attributes: &HashMap<String, TagStructureFieldAttributes>,
+) -> Vec<proc_macro2::TokenStream> {
+    data.fields.iter().filter_map(|field| {
+        if is_block_field(field, field_attributes) {
+            let field_name = &field.ident;
+            let offset = field_attributes.get(&field_name.as_ref().unwrap().to_string()).unwrap().offset;
+            return Some(quote! {
+                self.#field_name.load_blocks(source_index, adjusted_base + #offset, reader, structs, blocks, version)?;
+            });
+        }
+        None
+    }).collect()
+}
+
+/// Generates the statements collecting the references of a struct, including those of its blocks.
+fn field_references(
+    data: &syn::DataStruct,
+    field_attributes: &HashMap<String, TagStructureFieldAttributes>,
+) -> Vec<proc_macro2::TokenStream> {
+    data.fields
+        .iter()
+        .filter_map(|field| {
+            let field_name = &field.ident;
+            if is_block_field(field, field_attributes) {
+                Some(quote! {
+                    for element in &self.#field_name.elements {
+                        references.extend(infinite_rs::module::file::TagStructure::references(element));
+                    }
+                })
+            } else if is_type_named(&field.ty, "FieldReference") {
+                Some(quote! {
+                    references.push(&self.#field_name);
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Generates the statements counting the block elements of a struct, including nested ones.
+fn field_element_counts(
+    data: &syn::DataStruct,
+    field_attributes: &HashMap<String, TagStructureFieldAttributes>,
+) -> Vec<proc_macro2::TokenStream> {
+    data.fields
+        .iter()
+        .filter(|field| is_block_field(field, field_attributes))
+        .map(|field| {
+            let field_name = &field.ident;
+            quote! {
+                count += self.#field_name.elements.len();
+                for element in &self.#field_name.elements {
+                    count += infinite_rs::module::file::TagStructure::element_count(element);
+                }
+            }
+        })
+        .collect()
+}
+
 fn tag_structure_derive2(
     input: proc_macro2::TokenStream,
 ) -> deluxe::Result<proc_macro2::TokenStream> {
@@ -52,49 +173,61 @@ fn tag_structure_derive2(
         panic!("TagStructure can only be derived for structs")
     };
 
-    let (name, field_offset): (Vec<String>, Vec<u64>) = field_attributes
-        .clone()
-        .into_iter()
-        .map(|(field, attrs)| (field, attrs.offset))
+    // Built from the fields in declaration order, so that names and offsets are always paired correctly
+    // and the generated code does not depend on the iteration order of the HashMap.
+    let (name, field_offset): (Vec<String>, Vec<u64>) = data
+        .fields
+        .iter()
+        .map(|field| {
+            let field_name = field.ident.as_ref().unwrap().to_string();
+            let offset = field_attributes.get(&field_name).unwrap().offset;
+            (field_name, offset)
+        })
         .unzip();
 
-    let field_reads = data.fields.iter().map(|field| {
-        let field_name = &field.ident;
-        let offset = field_attributes
-            .get(&field_name.as_ref().unwrap().to_string())
-            .unwrap()
-            .offset;
-        quote! {
-            reader.seek(std::io::SeekFrom::Start(main_offset + #offset))?;
-            self.#field_name.read(reader)?;
-        }
-    });
-
-    let field_blocks = data.fields.iter().filter_map(|field| {
-        if let syn::Type::Path(type_path) = &field.ty {
-            if let Some(segment) = type_path.path.segments.last() {
-                if segment.ident == "FieldBlock" {
-                    let field_name = &field.ident;
-                    let offset = field_attributes.get(&field_name.as_ref().unwrap().to_string()).unwrap().offset;
-                    return Some(quote! {
-                        self.#field_name.load_blocks(source_index, adjusted_base + #offset, reader, structs, blocks)?;
-                    });
-                }
+    let mut sorted_fields: Vec<(String, u64)> = name
+        .iter()
+        .cloned()
+        .zip(field_offset.iter().copied())
+        .collect();
+    sorted_fields.sort_by_key(|&(_, offset)| offset);
+    let (sorted_name, sorted_offset): (Vec<String>, Vec<u64>) = sorted_fields.into_iter().unzip();
+
+    let field_reads = field_reads(data, &field_attributes);
+    let field_blocks = field_blocks(data, &field_attributes);
+    let field_references = field_references(data, &field_attributes);
+    let field_element_counts = field_element_counts(data, &field_attributes);
+
+    Ok(quote! {
+        impl #impl_generics #ident #type_generics #where_clause {
+            /// Reads every field of the struct, skipping the ones whose `min_version` is newer than `version` if any.
+            #[doc(hidden)]
+            fn read_fields<R: infinite_rs::common::extensions::BufReaderExt>(
+                &mut self,
+                reader: &mut R,
+                version: Option<&infinite_rs::module::header::ModuleVersion>,
+            ) -> infinite_rs::Result<()> {
+                let main_offset = reader.stream_position()?;
+                #(#field_reads)*
+                reader.seek(std::io::SeekFrom::Start(main_offset + infinite_rs::module::file::TagStructure::size(self)))?;
+                Ok(())
             }
         }
-        None
-    });
 
-    Ok(quote! {
         impl #impl_generics infinite_rs::module::file::TagStructure for #ident #type_generics #where_clause {
             fn size(&mut self) -> u64 {
                 #size
             }
             fn read<R: infinite_rs::common::extensions::BufReaderExt>(&mut self, reader: &mut R) -> infinite_rs::Result<()> {
-                let main_offset = reader.stream_position()?;
-                #(#field_reads)*
-                reader.seek(std::io::SeekFrom::Start(main_offset + self.size()))?;
-                Ok(())
+                self.read_fields(reader, None)
+            }
+
+            fn read_versioned<R: infinite_rs::common::extensions::BufReaderExt>(
+                &mut self,
+                reader: &mut R,
+                version: &infinite_rs::module::header::ModuleVersion,
+            ) -> infinite_rs::Result<()> {
+                self.read_fields(reader, Some(version))
             }
 
             fn offsets(&self) -> std::collections::HashMap<&'static str, u64> {
@@ -105,6 +238,24 @@ fn tag_structure_derive2(
                 map
             }
 
+            fn offsets_sorted(&self) -> Vec<(&'static str, u64)> {
+                vec![#((#sorted_name, #sorted_offset)),*]
+            }
+
+            #[allow(unused_mut)]
+            fn references(&self) -> Vec<&infinite_rs::tag::types::common_types::FieldReference> {
+                let mut references = Vec::new();
+                #(#field_references)*
+                references
+            }
+
+            #[allow(unused_mut)]
+            fn element_count(&self) -> usize {
+                let mut count = 0;
+                #(#field_element_counts)*
+                count
+            }
+
             fn load_field_blocks<R: std::io::BufRead + std::io::Seek + infinite_rs::common::extensions::BufReaderExt>(
                 &mut self,
                 source_index: i32,
@@ -112,6 +263,7 @@ fn tag_structure_derive2(
                 reader: &mut R,
                 structs: &[infinite_rs::tag::structure::TagStruct],
                 blocks: &[infinite_rs::tag::datablock::TagDataBlock],
+                version: &infinite_rs::module::header::ModuleVersion,
             ) -> infinite_rs::Result<()> {
                 #(#field_blocks)*
                 Ok(())
@@ -0,0 +1,484 @@
+use std::io::{BufReader, Cursor};
+
+use infinite_rs::common::errors::TagError;
+use infinite_rs::module::file::{ModuleFileEntry, TagStructure};
+use infinite_rs::module::header::ModuleVersion;
+use infinite_rs::tag::datablock::TagDataBlock;
+use infinite_rs::tag::loader::TagFile;
+use infinite_rs::tag::structure::{TagStruct, TagStructType};
+use infinite_rs::tag::types::common_types::{
+    FieldBlock, FieldCharBlockIndex, FieldLongBlockIndex, FieldLongInteger, FieldReference,
+    FieldShortBlockIndex,
+};
+use infinite_rs::Error;
+use infinite_rs_derive::TagStructure;
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x8))]
+struct TwoFields {
+    #[data(offset(0x0))]
+    first: FieldLongInteger,
+    #[data(offset(0x4))]
+    second: FieldLongInteger,
+}
+
+#[test]
+/// Verifies that a failing field read reports the name and offset of the field.
+fn test_read_error_names_field() {
+    let data = vec![1, 0, 0, 0, 2, 0];
+    let mut reader = BufReader::new(Cursor::new(data));
+    let mut two_fields = TwoFields::default();
+
+    let error = two_fields.read(&mut reader).unwrap_err();
+    match error {
+        Error::TagError(TagError::FieldReadError { field, offset, .. }) => {
+            assert_eq!(field, "second");
+            assert_eq!(offset, 0x4);
+        }
+        _ => panic!("unexpected error: {error:?}"),
+    }
+    assert_eq!(two_fields.first.0, 1);
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0xC))]
+struct UnorderedFields {
+    #[data(offset(0x8))]
+    last: FieldLongInteger,
+    #[data(offset(0x0))]
+    first: FieldLongInteger,
+    #[data(offset(0x4))]
+    middle: FieldLongInteger,
+}
+
+#[test]
+/// Verifies that sorted offsets are ordered by offset and can be looked up in reverse.
+fn test_offsets_sorted() {
+    let fields = UnorderedFields::default();
+    assert_eq!(
+        fields.offsets_sorted(),
+        vec![("first", 0x0), ("middle", 0x4), ("last", 0x8)]
+    );
+    assert_eq!(fields.field_at_offset(0x4), Some("middle"));
+    assert_eq!(fields.field_at_offset(0x2), None);
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x4))]
+struct BlockElement {
+    #[data(offset(0x0))]
+    value: FieldLongInteger,
+}
+
+/// Aliased blocks are not detected by their type name, and need to be marked with `#[data(block)]`.
+type ElementBlock = FieldBlock<BlockElement>;
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x14))]
+struct AliasedBlock {
+    #[data(offset(0x0), block)]
+    elements: ElementBlock,
+}
+
+#[test]
+/// Verifies that fields marked with `#[data(block)]` are loaded even if their type is aliased.
+fn test_aliased_block_loaded() {
+    let mut data = vec![0u8; 0x14];
+    data[0x10] = 1; // block size
+    data.extend_from_slice(&7i32.to_le_bytes());
+    let mut reader = BufReader::new(Cursor::new(data));
+
+    let structure = TagStruct::default(); // field block 0 at offset 0, pointing to datablock 0
+    let mut block = TagDataBlock::default();
+    block.offset = 0x14;
+
+    let mut aliased = AliasedBlock::default();
+    aliased.read(&mut reader).unwrap();
+    aliased
+        .load_field_blocks(
+            0,
+            0,
+            &mut reader,
+            &[structure],
+            &[block],
+            &ModuleVersion::Season3,
+        )
+        .unwrap();
+
+    assert_eq!(aliased.elements.elements.len(), 1);
+    assert_eq!(aliased.elements.elements[0].value.0, 7);
+    assert!(aliased.elements.was_loaded());
+}
+
+#[test]
+/// Verifies that block indices resolve to elements of the block, with the maximum value used as a null index.
+fn test_block_index_resolve() {
+    let mut block = ElementBlock::default();
+    for value in [10, 20] {
+        let mut element = BlockElement::default();
+        element.value.0 = value;
+        block.elements.push(element);
+    }
+
+    assert_eq!(FieldShortBlockIndex(1).resolve(&block).unwrap().value.0, 20);
+    assert_eq!(FieldCharBlockIndex(0).resolve(&block).unwrap().value.0, 10);
+    assert!(FieldCharBlockIndex(2).resolve(&block).is_none());
+    assert!(FieldShortBlockIndex(0xFFFF).resolve(&block).is_none());
+    assert!(FieldLongBlockIndex(u32::MAX).resolve(&block).is_none());
+}
+
+#[test]
+/// Verifies that a block read from the wrong offset is reported as not loaded, while an empty block is.
+fn test_block_not_loaded() {
+    let mut data = vec![0u8; 0x14];
+    data[0x10] = 1; // block size
+    let mut reader = BufReader::new(Cursor::new(data));
+
+    // The struct is expected at offset 0x8, so the block at offset 0 has nothing pointing to it.
+    let mut structure = TagStruct::default();
+    structure.field_offset = 0x8;
+
+    let mut aliased = AliasedBlock::default();
+    aliased.read(&mut reader).unwrap();
+    assert!(!aliased.elements.was_loaded());
+    aliased
+        .load_field_blocks(
+            0,
+            0,
+            &mut reader,
+            &[structure],
+            &[TagDataBlock::default()],
+            &ModuleVersion::Season3,
+        )
+        .unwrap();
+    assert!(aliased.elements.elements.is_empty());
+    assert!(!aliased.elements.was_loaded());
+
+    let mut empty = AliasedBlock::default();
+    empty
+        .load_field_blocks(0, 0, &mut reader, &[], &[], &ModuleVersion::Season3)
+        .unwrap();
+    assert!(empty.elements.was_loaded());
+}
+
+#[test]
+/// Verifies that `offsets` maps each field to its declared offset.
+fn test_offsets_match_declaration() {
+    let fields = UnorderedFields::default();
+    let offsets = fields.offsets();
+    assert_eq!(offsets.len(), 3);
+    assert_eq!(offsets.get("last"), Some(&0x8));
+    assert_eq!(offsets.get("first"), Some(&0x0));
+    assert_eq!(offsets.get("middle"), Some(&0x4));
+}
+
+#[test]
+/// Verifies that the raw bytes of the main struct are returned along with the parsed struct.
+fn test_read_metadata_with_bytes() {
+    let mut main_struct = TagStruct::default();
+    main_struct.struct_type = TagStructType::MainStruct;
+    let mut main_block = TagDataBlock::default();
+    main_block.offset = 0x4;
+    main_block.entry_size = 0x8;
+
+    let mut entry = ModuleFileEntry::default();
+    entry.tag_info = Some(TagFile {
+        struct_definitions: vec![main_struct],
+        datablock_definitions: vec![main_block],
+        ..Default::default()
+    });
+    entry.data_stream = Some(BufReader::new(Cursor::new(vec![
+        0xFF, 0xFF, 0xFF, 0xFF, 1, 0, 0, 0, 2, 0, 0, 0, 0xEE,
+    ])));
+
+    let mut two_fields = TwoFields::default();
+    let bytes = entry.read_metadata_with_bytes(&mut two_fields).unwrap();
+    assert_eq!(bytes, vec![1, 0, 0, 0, 2, 0, 0, 0]);
+    assert_eq!(two_fields.first.0, 1);
+    assert_eq!(two_fields.second.0, 2);
+}
+
+#[test]
+/// Verifies that the main struct is read from its datablock offset as-is, even if it is aligned inside the tag data
+/// and the header size is not a multiple of the alignment.
+fn test_read_metadata_aligned_main_block() {
+    let mut main_struct = TagStruct::default();
+    main_struct.struct_type = TagStructType::MainStruct;
+    let mut main_block = TagDataBlock::default();
+    main_block.offset = 0x10;
+    main_block.entry_size = 0x8;
+
+    let mut data = vec![0xFF; 0x10]; // Padding up to the 16 byte boundary.
+    data.extend_from_slice(&[3, 0, 0, 0, 4, 0, 0, 0]);
+    let mut entry = ModuleFileEntry::default();
+    entry.uncompressed_header_size = 0x54;
+    entry.uncompressed_tag_data_size = 0x18;
+    entry.total_uncompressed_size = 0x54 + 0x18;
+    entry.tag_info = Some(TagFile {
+        struct_definitions: vec![main_struct],
+        datablock_definitions: vec![main_block],
+        ..Default::default()
+    });
+    entry.data_stream = Some(BufReader::new(Cursor::new(data)));
+
+    let mut two_fields = TwoFields::default();
+    entry.read_metadata(&mut two_fields).unwrap();
+    assert_eq!(two_fields.first.0, 3);
+    assert_eq!(two_fields.second.0, 4);
+}
+
+#[test]
+/// Verifies that tags without datablocks, or whose main struct has no datablock, return an error instead of panicking.
+fn test_read_metadata_without_datablocks() {
+    let mut main_struct = TagStruct::default();
+    main_struct.struct_type = TagStructType::MainStruct;
+
+    let mut entry = ModuleFileEntry::default();
+    entry.tag_info = Some(TagFile {
+        struct_definitions: vec![main_struct],
+        ..Default::default()
+    });
+    entry.data_stream = Some(BufReader::new(Cursor::new(Vec::new())));
+    assert!(matches!(
+        entry.read_metadata(&mut TwoFields::default()),
+        Err(Error::TagError(TagError::BlockIndexOutOfRange(0)))
+    ));
+
+    let mut main_struct = TagStruct::default();
+    main_struct.struct_type = TagStructType::MainStruct;
+    main_struct.target_index = -1;
+    entry.tag_info = Some(TagFile {
+        struct_definitions: vec![main_struct],
+        datablock_definitions: vec![TagDataBlock::default()],
+        ..Default::default()
+    });
+    assert!(matches!(
+        entry.read_metadata(&mut TwoFields::default()),
+        Err(Error::TagError(TagError::BlockIndexOutOfRange(-1)))
+    ));
+
+    entry.tag_info = Some(TagFile::default());
+    assert!(matches!(
+        entry.read_metadata(&mut TwoFields::default()),
+        Err(Error::TagError(TagError::MainStructNotFound))
+    ));
+}
+
+#[test]
+/// Verifies that a header size larger than the total size of the file returns an error instead of underflowing.
+fn test_read_metadata_invalid_header_size() {
+    let mut entry = ModuleFileEntry::default();
+    entry.uncompressed_header_size = 0x100;
+    entry.total_uncompressed_size = 0x10;
+    entry.data_stream = Some(BufReader::new(Cursor::new(vec![0; 0x10])));
+
+    assert!(matches!(
+        entry.read_metadata(&mut TwoFields::default()),
+        Err(Error::TagError(TagError::InvalidHeaderSize(0x100, 0x10)))
+    ));
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x14))]
+struct NestedElement {
+    #[data(offset(0x0))]
+    children: FieldBlock<BlockElement>,
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x14))]
+struct NestedRoot {
+    #[data(offset(0x0))]
+    elements: FieldBlock<NestedElement>,
+}
+
+/// Builds a loaded entry whose main struct contains two [`NestedElement`]s, containing one and two [`BlockElement`]s.
+/// The GUID of each block struct is the index of its datablock.
+fn nested_blocks_entry() -> ModuleFileEntry {
+    let mut main_struct = TagStruct::default();
+    main_struct.struct_type = TagStructType::MainStruct;
+    main_struct.field_block = -1;
+
+    let mut structs = vec![main_struct];
+    // (parent datablock, offset in parent datablock, target datablock)
+    for (field_block, field_offset, target_index) in [(0, 0x0, 1), (1, 0x0, 2), (1, 0x14, 3)] {
+        let mut structure = TagStruct::default();
+        structure.guid = u128::try_from(target_index).unwrap();
+        structure.struct_type = TagStructType::TagBlock;
+        structure.field_block = field_block;
+        structure.field_offset = field_offset;
+        structure.target_index = target_index;
+        structs.push(structure);
+    }
+
+    let mut blocks = Vec::new();
+    for (offset, entry_size) in [(0x8, 0x14), (0x1C, 0x28), (0x44, 0x4), (0x48, 0x8)] {
+        let mut block = TagDataBlock::default();
+        block.offset = offset;
+        block.entry_size = entry_size;
+        blocks.push(block);
+    }
+
+    let mut data = vec![0xFF; 0x8]; // Data before the main struct.
+    for size in [2u32, 1, 2] {
+        data.extend_from_slice(&[0; 0x10]);
+        data.extend_from_slice(&size.to_le_bytes());
+    }
+    for value in [5i32, 6, 7] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let mut entry = ModuleFileEntry::default();
+    entry.total_uncompressed_size = u32::try_from(data.len()).unwrap();
+    entry.tag_info = Some(TagFile {
+        struct_definitions: structs,
+        datablock_definitions: blocks,
+        ..Default::default()
+    });
+    entry.data_stream = Some(BufReader::new(Cursor::new(data)));
+    entry
+}
+
+#[test]
+/// Verifies that blocks nested inside block elements are found with the offset of their element, and that datablock
+/// offsets are taken from the start of the tag data even if the main struct does not start there.
+fn test_read_metadata_nested_blocks() {
+    let mut entry = nested_blocks_entry();
+    let mut root = NestedRoot::default();
+    entry.read_metadata(&mut root).unwrap();
+
+    let elements = &root.elements.elements;
+    assert_eq!(elements.len(), 2);
+    let values = |element: &NestedElement| -> Vec<i32> {
+        element
+            .children
+            .elements
+            .iter()
+            .map(|child| child.value.0)
+            .collect()
+    };
+    assert_eq!(values(&elements[0]), vec![5]);
+    assert_eq!(values(&elements[1]), vec![6, 7]);
+    assert_eq!(root.element_count(), 5);
+}
+
+#[test]
+/// Verifies that the elements of a block are read by the GUID of its struct, including their nested blocks, and that
+/// unknown GUIDs are reported.
+fn test_read_block_as() {
+    let entry = nested_blocks_entry();
+
+    let elements = entry.read_block_as::<NestedElement>(1).unwrap();
+    assert_eq!(elements.len(), 2);
+    assert_eq!(elements[0].children.elements[0].value.0, 5);
+    assert_eq!(elements[1].children.elements.len(), 2);
+
+    let children = entry.read_block_as::<BlockElement>(3).unwrap();
+    let values: Vec<i32> = children.iter().map(|child| child.value.0).collect();
+    assert_eq!(values, vec![6, 7]);
+
+    assert!(matches!(
+        entry.read_block_as::<BlockElement>(4),
+        Err(Error::TagError(TagError::StructNotFound(4)))
+    ));
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x1C))]
+struct MaterialParameter {
+    #[data(offset(0x00))]
+    bitmap: FieldReference,
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x50))]
+struct MaterialTag {
+    #[data(offset(0x10))]
+    material_shader: FieldReference,
+    #[data(offset(0x2C))]
+    material_parameters: FieldBlock<MaterialParameter>,
+    #[data(offset(0x40))]
+    flags: FieldLongInteger,
+}
+
+#[test]
+/// Verifies that references are collected from fields and from elements of field blocks.
+fn test_references() {
+    let mut material = MaterialTag::default();
+    assert_eq!(material.references().len(), 1);
+
+    material.material_parameters.elements =
+        vec![MaterialParameter::default(), MaterialParameter::default()];
+    assert_eq!(material.references().len(), 3);
+}
+
+#[test]
+/// Verifies that elements of field blocks are counted along with the elements of blocks nested inside them.
+fn test_element_count() {
+    let mut material = MaterialTag::default();
+    assert_eq!(material.element_count(), 0);
+
+    material.material_parameters.elements =
+        vec![MaterialParameter::default(), MaterialParameter::default()];
+    assert_eq!(material.element_count(), 2);
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x8))]
+struct VersionedFields {
+    #[data(offset(0x0))]
+    first: FieldLongInteger,
+    #[data(offset(0x4), min_version(53))]
+    added: FieldLongInteger,
+}
+
+#[test]
+/// Verifies that fields newer than the module version are skipped, and that the whole struct is still consumed.
+fn test_read_versioned() {
+    let data = [1, 0, 0, 0, 2, 0, 0, 0];
+    for (version, expected) in [(ModuleVersion::Release, 0), (ModuleVersion::Season3, 2)] {
+        let mut reader = Cursor::new(&data[..]);
+        let mut fields = VersionedFields::default();
+        fields.read_versioned(&mut reader, &version).unwrap();
+        assert_eq!(fields.first.0, 1);
+        assert_eq!(fields.added.0, expected);
+        assert_eq!(reader.position(), 8);
+    }
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x14))]
+struct VersionedBlock {
+    #[data(offset(0x0))]
+    elements: FieldBlock<VersionedFields>,
+}
+
+#[test]
+/// Verifies that fields newer than the module version are also skipped in the elements of field blocks.
+fn test_read_versioned_block() {
+    let mut data = vec![0u8; 0x14];
+    data[0x10] = 2; // block size
+    for value in [1i32, 2, 3, 4] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    for (version, expected) in [
+        (ModuleVersion::Release, [0, 0]),
+        (ModuleVersion::Season3, [2, 4]),
+    ] {
+        let mut reader = BufReader::new(Cursor::new(data.clone()));
+        let structure = TagStruct::default(); // field block 0 at offset 0, pointing to datablock 0
+        let mut block = TagDataBlock::default();
+        block.offset = 0x14;
+
+        let mut root = VersionedBlock::default();
+        root.read_versioned(&mut reader, &version).unwrap();
+        root.load_field_blocks(0, 0, &mut reader, &[structure], &[block], &version)
+            .unwrap();
+
+        let elements = &root.elements.elements;
+        assert_eq!(elements.len(), 2);
+        assert_eq!([elements[0].first.0, elements[1].first.0], [1, 3]);
+        assert_eq!([elements[0].added.0, elements[1].added.0], expected);
+    }
+}
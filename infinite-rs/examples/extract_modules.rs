@@ -1,11 +1,12 @@
 use std::{
     fs::{create_dir_all, File},
-    io::{BufWriter, Read, Seek, Write},
-    path::{Path, PathBuf},
+    io::{BufWriter, Write},
+    path::PathBuf,
 };
 
 use argh::FromArgs;
-use infinite_rs::{ModuleFile, Result};
+use infinite_rs::module::set::ModuleSet;
+use infinite_rs::Result;
 
 #[derive(FromArgs, Debug)]
 /// Tool that extracts files from modules of any version from Halo Infinite
@@ -13,53 +14,65 @@ struct InfiniteExtract {
     /// path to where modules are stored (deploy folder)
     #[argh(option)]
     deploy_path: PathBuf,
-    /// path to folder to output files to.
+    /// path to folder to output files to, required unless --info is given.
     #[argh(option)]
-    output_path: PathBuf,
-}
-
-fn load_modules<R: AsRef<Path>>(deploy_path: R) -> Result<Vec<ModuleFile>> {
-    let mut modules = Vec::new();
-    for entry in walkdir::WalkDir::new(deploy_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            let file_path = entry.path().to_str().unwrap();
-            if file_path.ends_with(".module") {
-                let module = ModuleFile::from_path(file_path)?;
-                modules.push(module);
-            }
-        }
-    }
-    Ok(modules)
+    output_path: Option<PathBuf>,
+    /// split each tag into header, tag data, resource data and actual resource files.
+    #[argh(switch)]
+    split_sections: bool,
+    /// print a summary of each module instead of extracting files.
+    #[argh(switch)]
+    info: bool,
 }
 
 fn main() -> Result<()> {
     let args: InfiniteExtract = argh::from_env();
-    let mut modules = load_modules(args.deploy_path)?;
-    for module in &mut modules {
-        for idx in 0..module.files.len() {
-            module.read_tag(idx as u32)?;
+    let mut modules = ModuleSet::from_deploy(&[args.deploy_path])?;
+    if args.info {
+        for module in &modules.modules {
+            println!("{}", module.inventory());
+        }
+        return Ok(());
+    }
+    let Some(output_path) = args.output_path else {
+        eprintln!("--output-path is required to extract files");
+        return Ok(());
+    };
+    for module in &mut modules.modules {
+        for (index, result) in module.read_all_tags_lenient() {
+            if let Err(error) = result {
+                eprintln!("failed to read file {index}: {error}");
+            }
         }
 
         for file in &mut module.files {
-            let mut buffer = Vec::with_capacity(file.total_uncompressed_size as usize);
-            if let Some(stream) = file.data_stream.as_mut() {
-                stream.rewind()?;
-                stream.read_to_end(&mut buffer)?;
-            }
             let tag_path = file
                 .tag_name
                 .replace(" ", "_")
                 .replace("*", "_")
                 .replace(r"\", "/")
                 .replace(":", "_");
-            let path = PathBuf::from(&args.output_path).join(tag_path);
+            let path = output_path.join(tag_path);
             create_dir_all(path.parent().unwrap())?;
-            let filee = File::create(path)?;
-            let mut bw = BufWriter::new(&filee);
-            bw.write_all(&buffer)?;
+            if args.split_sections && file.data_stream.is_some() {
+                let sections = file.split_sections()?;
+                for (extension, section) in [
+                    ("header", &sections.header),
+                    ("data", &sections.tag_data),
+                    ("resource", &sections.resource_data),
+                    ("actual_resource", &sections.actual_resource),
+                ] {
+                    let mut section_path = path.clone().into_os_string();
+                    section_path.push(format!(".{extension}"));
+                    File::create(section_path)?.write_all(section)?;
+                }
+                continue;
+            }
+            let mut bw = BufWriter::new(File::create(path)?);
+            if file.data_stream.is_some() {
+                file.copy_to(&mut bw)?;
+            }
+            bw.flush()?;
         }
     }
     Ok(())
@@ -1,30 +1,12 @@
-use std::path::Path;
-
 use bitflags::bitflags;
+use infinite_rs::module::set::ModuleSet;
 use infinite_rs::tag::types::common_types::{
     AnyTag, FieldBlock, FieldByteFlags, FieldCharEnum, FieldLongEnum, FieldReference, FieldStringId,
 };
-use infinite_rs::{ModuleFile, Result};
+use infinite_rs::Result;
 use infinite_rs_derive::TagStructure;
 use num_enum::TryFromPrimitive;
 
-fn load_modules<R: AsRef<Path>>(deploy_path: R) -> Result<Vec<ModuleFile>> {
-    let mut modules = Vec::new();
-    for entry in walkdir::WalkDir::new(deploy_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            let file_path = entry.path().to_str().unwrap();
-            if file_path.ends_with(".module") {
-                let module = ModuleFile::from_path(file_path)?;
-                modules.push(module);
-            }
-        }
-    }
-    Ok(modules)
-}
-
 #[derive(Default, Debug, TagStructure)]
 #[data(size(0x30))]
 struct MaterialShaderFunctionParameter {
@@ -59,6 +41,8 @@ enum MaterialParameterType {
 #[derive(Default, Debug, TagStructure)]
 #[data(size(0x9c))]
 struct MaterialParameter {
+    #[data(offset(0x0))]
+    parameter_name: FieldStringId,
     #[data(offset(0x8))]
     bitmap: FieldReference,
     #[data(offset(0x4))]
@@ -128,6 +112,25 @@ struct MaterialStyleInfo {
     requires_damage: FieldCharEnum<MaterialStyleShaderSupportsDamageEnum>,
 }
 
+impl MaterialStyleInfo {
+    /// Returns the name and string ID of every intention the style overrides.
+    fn active_intentions(&self) -> Vec<(&'static str, u32)> {
+        [
+            ("base", &self.base_intention),
+            ("mask0_red_channel", &self.mask0_red_channel_intention),
+            ("mask0_green_channel", &self.mask0_green_channel_intention),
+            ("mask0_blue_channel", &self.mask0_blue_channel_intention),
+            ("mask1_red_channel", &self.mask1_red_channel_intention),
+            ("mask1_green_channel", &self.mask1_green_channel_intention),
+            ("mask1_blue_channel", &self.mask1_blue_channel_intention),
+        ]
+        .into_iter()
+        .filter(|(_, intention)| intention.is_set())
+        .map(|(name, intention)| (name, intention.0 as u32))
+        .collect()
+    }
+}
+
 #[derive(Default, Debug, TagStructure)]
 #[data(size(0x88))]
 struct MaterialTag {
@@ -145,22 +148,94 @@ struct MaterialTag {
     style_info: FieldBlock<MaterialStyleInfo>,
 }
 
+impl MaterialTag {
+    /// Returns the name of each bitmap parameter of the material along with the bitmap bound to it.
+    ///
+    /// Names are string IDs, hashed the same way as in every other tag.
+    fn texture_bindings(&self) -> Vec<(u32, &FieldReference)> {
+        self.material_parameters
+            .elements
+            .iter()
+            .filter(|parameter| matches!(parameter.parameter_type.0, MaterialParameterType::Bitmap))
+            .map(|parameter| (parameter.parameter_name.0 as u32, &parameter.bitmap))
+            .collect()
+    }
+}
+
 fn main() -> Result<()> {
-    let mut modules = load_modules(String::from("C:/XboxGames/Halo Infinite/Content/deploy/"))?;
-
-    for module in &mut modules {
-        for index in 0..module.files.len() {
-            let tag = module.read_tag(index as u32)?;
-            if let Some(tag) = tag {
-                if tag.tag_group == "mat " {
-                    let mut mat = MaterialTag::default();
-                    tag.read_metadata(&mut mat)?;
+    let mut modules = ModuleSet::from_deploy(&["C:/XboxGames/Halo Infinite/Content/deploy/"])?;
+
+    for module in &mut modules.modules {
+        for mat in module.iter_metadata::<MaterialTag>("mat ") {
+            let (_, mat) = mat?;
+            for (name, bitmap) in mat.texture_bindings() {
+                println!("{name:08X} -> {:08X}", bitmap.global_id);
+            }
+            for style in &mat.style_info.elements {
+                for (channel, intention) in style.active_intentions() {
+                    println!("{channel}: {intention:08X}");
                 }
-                // explicitly drop buffer to free up memory
-                // normally, can take 50+ GBs of RAM
-                module.files[index].data_stream = None
             }
         }
     }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a material parameter of the given type, bound to the bitmap with the given global ID.
+    fn parameter(
+        name: i32,
+        parameter_type: MaterialParameterType,
+        global_id: i32,
+    ) -> MaterialParameter {
+        let mut parameter = MaterialParameter {
+            parameter_name: FieldStringId(name),
+            parameter_type: FieldLongEnum(parameter_type),
+            ..Default::default()
+        };
+        parameter.bitmap.global_id = global_id;
+        parameter
+    }
+
+    #[test]
+    /// Verifies that only bitmap parameters are returned, along with their names and bound bitmaps.
+    fn test_texture_bindings() {
+        let mut mat = MaterialTag::default();
+        mat.material_parameters.elements = vec![
+            parameter(0x1111, MaterialParameterType::Bitmap, 0x0A),
+            parameter(0x2222, MaterialParameterType::Real, -1),
+            parameter(0x3333, MaterialParameterType::Color, -1),
+            parameter(0x4444, MaterialParameterType::Bitmap, 0x0B),
+        ];
+        let bindings: Vec<(u32, i32)> = mat
+            .texture_bindings()
+            .into_iter()
+            .map(|(name, bitmap)| (name, bitmap.global_id))
+            .collect();
+        assert_eq!(bindings, [(0x1111, 0x0A), (0x4444, 0x0B)]);
+    }
+
+    #[test]
+    /// Verifies that intentions left unset are skipped and that the rest are returned in channel order.
+    fn test_active_intentions() {
+        let style = MaterialStyleInfo {
+            base_intention: FieldStringId(0x0100),
+            mask0_green_channel_intention: FieldStringId(-0x0200),
+            mask1_blue_channel_intention: FieldStringId(0x0300),
+            ..Default::default()
+        };
+        assert_eq!(
+            style.active_intentions(),
+            [
+                ("base", 0x0100),
+                ("mask0_green_channel", 0xFFFF_FE00),
+                ("mask1_blue_channel", 0x0300),
+            ]
+        );
+        assert!(MaterialStyleInfo::default().active_intentions().is_empty());
+    }
+}
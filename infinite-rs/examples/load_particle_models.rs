@@ -0,0 +1,168 @@
+use infinite_rs::module::file::TagStructure;
+use infinite_rs::module::set::ModuleSet;
+use infinite_rs::tag::types::common_types::{
+    AnyTag, FieldBlock, FieldReference, FieldStringId, FieldWordInteger,
+};
+use infinite_rs::{Result, TagId};
+use infinite_rs_derive::TagStructure;
+
+const DEPLOY_PATH: &str = "C:/XboxGames/Halo Infinite/Content/deploy/";
+const PARTICLE_MODEL_GROUP: &str = "pmdf";
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x40))]
+/// Emitter of a particle model, spawning particles from a shape.
+struct ParticleModelEmitter {
+    #[data(offset(0x00))]
+    name: FieldStringId,
+    /// Render model used as the emission shape of "custom" emitters.
+    #[data(offset(0x04))]
+    custom_shape: FieldReference,
+    #[data(offset(0x20))]
+    location: FieldWordInteger,
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x60))]
+struct ParticleModelTag {
+    #[data(offset(0x00))]
+    any_tag: AnyTag,
+    /// Render model whose meshes are drawn for every particle.
+    #[data(offset(0x10))]
+    render_model: FieldReference,
+    /// Bitmap used by the particles.
+    #[data(offset(0x2C))]
+    bitmap: FieldReference,
+    #[data(offset(0x48))]
+    emitters: FieldBlock<ParticleModelEmitter>,
+}
+
+impl ParticleModelTag {
+    /// Counts the emitters of the particle model.
+    fn emitter_count(&self) -> usize {
+        self.emitters.elements.len()
+    }
+}
+
+fn main() -> Result<()> {
+    let mut modules = ModuleSet::from_deploy(&[DEPLOY_PATH])?;
+
+    let mut particle_models = Vec::new();
+    for module in &mut modules.modules {
+        let loaded = module
+            .iter_metadata::<ParticleModelTag>(PARTICLE_MODEL_GROUP)
+            .collect::<Result<Vec<_>>>()?;
+        for (index, particle_model) in loaded {
+            // Render models, bitmaps and every other tag referenced by the particle model.
+            let references = particle_model
+                .references()
+                .iter()
+                .filter(|reference| reference.global_id != -1)
+                .map(|reference| TagId(reference.global_id))
+                .collect::<Vec<_>>();
+            particle_models.push((
+                module.files[index].tag_name.clone(),
+                particle_model.emitter_count(),
+                references,
+            ));
+        }
+    }
+
+    for (name, emitters, references) in particle_models {
+        println!("{name}: {emitters} emitters");
+        for tag_id in references {
+            // References to tags that are not shipped in the deploy folder are skipped.
+            if let Some((module_index, file_index)) = modules.find_tag(tag_id) {
+                let file = &modules.modules[module_index].files[file_index.0];
+                println!("    {} ({})", file.tag_name, file.tag_group);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use infinite_rs::module::file::ModuleFileEntry;
+    use infinite_rs::tag::datablock::TagSectionType;
+
+    /// Size in bytes of the header of the tag, with two datablocks and two structs.
+    const HEADER_SIZE: u32 = 0xB0;
+
+    /// Builds a particle model tag with `emitters` emitters, each using the render model with the given global ID.
+    fn synthetic_particle_model(emitters: u32, render_model: i32) -> Vec<u8> {
+        let mut main = vec![0; 0x60];
+        main[0x18..0x1C].copy_from_slice(&render_model.to_le_bytes());
+        main[0x34..0x38].copy_from_slice(&(-1i32).to_le_bytes());
+        main[0x58..0x5C].copy_from_slice(&emitters.to_le_bytes());
+        let mut emitter_data = vec![0; 0x40 * emitters as usize];
+        for emitter in emitter_data.chunks_mut(0x40) {
+            emitter[0x0C..0x10].copy_from_slice(&render_model.to_le_bytes());
+        }
+        let blocks = [&main, &emitter_data];
+        let data_size: usize = blocks.iter().map(|block| block.len()).sum();
+
+        let mut tag = Vec::new();
+        // Header
+        tag.extend_from_slice(&0x6873_6375u32.to_le_bytes()); // magic
+        tag.extend_from_slice(&27i32.to_le_bytes()); // version
+        tag.extend_from_slice(&[0; 16]); // root struct guid and checksum
+        for count in [0u32, 2, 2, 0, 0] {
+            // dependencies, datablocks, structs, data references, tag references
+            tag.extend_from_slice(&count.to_le_bytes());
+        }
+        tag.extend_from_slice(&[0; 12]); // string table size, zoneset size, unknown
+        for size in [HEADER_SIZE, u32::try_from(data_size).unwrap(), 0, 0] {
+            // header, data, resource and actual resource sizes
+            tag.extend_from_slice(&size.to_le_bytes());
+        }
+        tag.extend_from_slice(&[0; 8]); // alignments and resource flag
+
+        // Datablocks
+        let mut offset = 0u64;
+        for block in blocks {
+            tag.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+            tag.extend_from_slice(&0u16.to_le_bytes()); // padding
+            tag.extend_from_slice(&(TagSectionType::TagData as u16).to_le_bytes());
+            tag.extend_from_slice(&offset.to_le_bytes());
+            offset += block.len() as u64;
+        }
+
+        // Structs: the main struct, then the emitter block it points to.
+        for (struct_type, target_index, field_block, field_offset) in
+            [(0u16, 0i32, -1i32, 0u32), (1, 1, 0, 0x48)]
+        {
+            tag.extend_from_slice(&[0; 16]); // guid
+            tag.extend_from_slice(&struct_type.to_le_bytes());
+            tag.extend_from_slice(&0u16.to_le_bytes()); // unknown
+            tag.extend_from_slice(&target_index.to_le_bytes());
+            tag.extend_from_slice(&field_block.to_le_bytes());
+            tag.extend_from_slice(&field_offset.to_le_bytes());
+        }
+        assert_eq!(tag.len(), HEADER_SIZE as usize);
+        for block in blocks {
+            tag.extend_from_slice(block);
+        }
+        tag
+    }
+
+    #[test]
+    /// Verifies that the emitters of the particle model are read from their block, along with their shapes.
+    fn test_particle_model_emitters() -> Result<()> {
+        let mut entry =
+            ModuleFileEntry::from_bytes(PARTICLE_MODEL_GROUP, synthetic_particle_model(3, 0x1234))?;
+        let mut particle_model = ParticleModelTag::default();
+        entry.read_metadata(&mut particle_model)?;
+
+        assert_eq!(particle_model.emitter_count(), 3);
+        assert_eq!(particle_model.render_model.global_id, 0x1234);
+        assert_eq!(particle_model.bitmap.global_id, -1);
+        assert!(particle_model
+            .emitters
+            .elements
+            .iter()
+            .all(|emitter| emitter.custom_shape.global_id == 0x1234));
+        Ok(())
+    }
+}
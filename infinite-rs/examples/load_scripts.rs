@@ -1,15 +1,15 @@
 use std::{
     fs::File,
-    io::{BufWriter, Read, Seek, SeekFrom, Write},
+    io::{BufWriter, Write},
 };
 
-use infinite_rs::{tag::types::common_types::FieldData, ModuleFile, Result};
+use infinite_rs::tag::{group::TagGroup, types::common_types::FieldData};
+use infinite_rs::{ModuleFile, Result};
 use infinite_rs_derive::TagStructure;
 
 const DEPLOY_PATH: &str =
     "C:/XboxGames/Halo Infinite/Content/deploy/any/globals/globals-rtx-new.module";
 const SAVE_PATH: &str = "./scripts";
-const SCRIPT_GROUP: &str = "hsc*";
 
 #[derive(Default, Debug, TagStructure)]
 #[data(size(0x2D8))]
@@ -23,29 +23,22 @@ struct HsSourceFileTag {
 fn main() -> Result<()> {
     let mut module = ModuleFile::from_path(DEPLOY_PATH)?;
     for idx in 0..module.files.len() {
-        if module.files[idx].tag_group == SCRIPT_GROUP {
+        if module.files[idx].group() == TagGroup::LuaScript {
             let tag = module.read_tag(idx as u32)?;
             if let Some(tag) = tag {
                 let mut source = HsSourceFileTag::default();
                 tag.read_metadata(&mut source)?;
 
-                let size = tag.uncompressed_header_size + 0x2D8;
-                let mut server_buf = vec![0; source.server.size as usize];
-                let mut client_buf = vec![0; source.client.size as usize];
-
-                if let Some(stream) = tag.data_stream.as_mut() {
-                    stream.seek(SeekFrom::Start(size as u64))?;
-                    stream.read_exact(&mut server_buf)?;
-                    stream.read_exact(&mut client_buf)?;
-                }
+                let server_buf = tag.read_field_data(&source.server)?.unwrap_or_default();
+                let client_buf = tag.read_field_data(&source.client)?.unwrap_or_default();
 
                 let server_file = File::create(format!("{SAVE_PATH}/{}_server.luac", tag.tag_id))?;
                 let mut bw = BufWriter::new(server_file);
-                bw.write_all(&server_buf)?;
+                bw.write_all(server_buf)?;
 
                 let client_file = File::create(format!("{SAVE_PATH}/{}_client.luac", tag.tag_id))?;
                 let mut bw = BufWriter::new(client_file);
-                bw.write_all(&client_buf)?;
+                bw.write_all(client_buf)?;
             }
         }
     }
@@ -0,0 +1,192 @@
+use infinite_rs::module::set::ModuleSet;
+use infinite_rs::tag::types::common_types::{
+    AnyTag, FieldBlock, FieldDwordInteger, FieldLongInteger, FieldStringId, FieldTagResource,
+};
+use infinite_rs::Result;
+use infinite_rs_derive::TagStructure;
+
+const DEPLOY_PATH: &str = "C:/XboxGames/Halo Infinite/Content/deploy/";
+const SOUND_GROUP: &str = "snd!";
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x1C))]
+/// Variation of a sound, one of which is picked at random when the sound is played.
+struct SoundPermutation {
+    #[data(offset(0x00))]
+    name: FieldStringId,
+    /// Number of samples of the encoded audio.
+    #[data(offset(0x08))]
+    sample_count: FieldDwordInteger,
+    /// Offset of the encoded audio of the permutation in the sound resource.
+    #[data(offset(0x10))]
+    resource_offset: FieldLongInteger,
+    /// Size in bytes of the encoded audio of the permutation in the sound resource.
+    #[data(offset(0x14))]
+    resource_size: FieldLongInteger,
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x48))]
+/// Range of pitches a set of permutations is played at.
+struct SoundPitchRange {
+    #[data(offset(0x00))]
+    name: FieldStringId,
+    #[data(offset(0x30))]
+    permutations: FieldBlock<SoundPermutation>,
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x128))]
+struct SoundTag {
+    #[data(offset(0x00))]
+    any_tag: AnyTag,
+    #[data(offset(0x60))]
+    pitch_ranges: FieldBlock<SoundPitchRange>,
+    /// Resource containing the encoded audio (such as FSB or WEM data) of every permutation.
+    #[data(offset(0x110))]
+    sound_resource: FieldTagResource,
+}
+
+impl SoundTag {
+    /// Counts the permutations of every pitch range.
+    fn permutation_count(&self) -> usize {
+        self.pitch_ranges
+            .elements
+            .iter()
+            .map(|pitch_range| pitch_range.permutations.elements.len())
+            .sum()
+    }
+}
+
+fn main() -> Result<()> {
+    let mut modules = ModuleSet::from_deploy(&[DEPLOY_PATH])?;
+
+    for module in &mut modules.modules {
+        let sounds = module
+            .iter_metadata::<SoundTag>(SOUND_GROUP)
+            .collect::<Result<Vec<_>>>()?;
+        for (index, sound) in sounds {
+            println!(
+                "{}: {} pitch ranges, {} permutations",
+                module.files[index].tag_name,
+                sound.pitch_ranges.elements.len(),
+                sound.permutation_count(),
+            );
+            // Audio is only located, not decoded.
+            for permutation in sound
+                .pitch_ranges
+                .elements
+                .iter()
+                .flat_map(|pitch_range| &pitch_range.permutations.elements)
+            {
+                println!(
+                    "    {:#X}: {} bytes at {:#X} ({} samples)",
+                    permutation.name.0,
+                    permutation.resource_size.0,
+                    permutation.resource_offset.0,
+                    permutation.sample_count.0,
+                );
+            }
+            if let Some(resource) = module.resolve_tag_resource(index as u32, &sound.sound_resource)
+            {
+                let resource = &module.files[resource];
+                println!(
+                    "    sound resource: {} ({} bytes)",
+                    resource.tag_name, resource.total_uncompressed_size
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use infinite_rs::module::file::ModuleFileEntry;
+    use infinite_rs::tag::datablock::TagSectionType;
+
+    /// Builds a sound tag with a pitch range for each permutation count.
+    fn synthetic_sound(permutation_counts: &[u32]) -> Vec<u8> {
+        let mut main = vec![0; 0x128];
+        let pitch_range_count = u32::try_from(permutation_counts.len()).unwrap();
+        main[0x70..0x74].copy_from_slice(&pitch_range_count.to_le_bytes());
+        let mut pitch_ranges = vec![0; 0x48 * permutation_counts.len()];
+        for (pitch_range, count) in pitch_ranges.chunks_mut(0x48).zip(permutation_counts) {
+            pitch_range[0x40..0x44].copy_from_slice(&count.to_le_bytes());
+        }
+        let mut blocks = vec![main, pitch_ranges];
+        // Structs are (type, target datablock, parent datablock, offset of the field in the parent).
+        let mut structs = vec![(0u16, 0i32, -1i32, 0u32), (1, 1, 0, 0x60)];
+        for (index, &count) in (0u32..).zip(permutation_counts) {
+            structs.push((
+                1,
+                i32::try_from(blocks.len()).unwrap(),
+                1,
+                0x48 * index + 0x30,
+            ));
+            blocks.push(vec![0; 0x1C * count as usize]);
+        }
+        let header_size = 0x50 + 0x10 * blocks.len() + 0x20 * structs.len();
+        let data_size: usize = blocks.iter().map(Vec::len).sum();
+
+        let mut tag = Vec::new();
+        // Header
+        tag.extend_from_slice(&0x6873_6375u32.to_le_bytes()); // magic
+        tag.extend_from_slice(&27i32.to_le_bytes()); // version
+        tag.extend_from_slice(&[0; 16]); // root struct guid and checksum
+        for count in [0, blocks.len(), structs.len(), 0, 0] {
+            // dependencies, datablocks, structs, data references, tag references
+            tag.extend_from_slice(&u32::try_from(count).unwrap().to_le_bytes());
+        }
+        tag.extend_from_slice(&[0; 12]); // string table size, zoneset size, unknown
+        for size in [header_size, data_size, 0, 0] {
+            // header, data, resource and actual resource sizes
+            tag.extend_from_slice(&u32::try_from(size).unwrap().to_le_bytes());
+        }
+        tag.extend_from_slice(&[0; 8]); // alignments and resource flag
+
+        // Datablocks
+        let mut offset = 0u64;
+        for block in &blocks {
+            tag.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+            tag.extend_from_slice(&0u16.to_le_bytes()); // padding
+            tag.extend_from_slice(&(TagSectionType::TagData as u16).to_le_bytes());
+            tag.extend_from_slice(&offset.to_le_bytes());
+            offset += block.len() as u64;
+        }
+
+        // Structs
+        for (struct_type, target_index, field_block, field_offset) in structs {
+            tag.extend_from_slice(&[0; 16]); // guid
+            tag.extend_from_slice(&struct_type.to_le_bytes());
+            tag.extend_from_slice(&0u16.to_le_bytes()); // unknown
+            tag.extend_from_slice(&target_index.to_le_bytes());
+            tag.extend_from_slice(&field_block.to_le_bytes());
+            tag.extend_from_slice(&field_offset.to_le_bytes());
+        }
+        assert_eq!(tag.len(), header_size);
+        for block in &blocks {
+            tag.extend_from_slice(block);
+        }
+        tag
+    }
+
+    #[test]
+    /// Verifies that the permutations of every pitch range are read and counted.
+    fn test_sound_permutation_count() -> Result<()> {
+        let mut entry = ModuleFileEntry::from_bytes(SOUND_GROUP, synthetic_sound(&[2, 0, 3]))?;
+        let mut sound = SoundTag::default();
+        entry.read_metadata(&mut sound)?;
+
+        let counts: Vec<usize> = sound
+            .pitch_ranges
+            .elements
+            .iter()
+            .map(|pitch_range| pitch_range.permutations.elements.len())
+            .collect();
+        assert_eq!(counts, [2, 0, 3]);
+        assert_eq!(sound.permutation_count(), 5);
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use std::borrow::Cow;
+use std::ffi::CStr;
+
+use infinite_rs::common::errors::TagError;
+use infinite_rs::tag::types::common_types::{
+    AnyTag, FieldBlock, FieldData, FieldLongInteger, FieldStringId,
+};
+use infinite_rs::{Error, ModuleFile, Result};
+use infinite_rs_derive::TagStructure;
+
+const MODULE_PATH: &str =
+    "C:/XboxGames/Halo Infinite/Content/deploy/any/globals/globals-rtx-new.module";
+const STRING_LIST_GROUP: &str = "unic";
+
+#[derive(Default, Debug, Clone, Copy)]
+#[repr(usize)]
+/// Languages that each string in a string list is stored in.
+enum Language {
+    #[default]
+    English,
+    Japanese,
+    German,
+    French,
+    Spanish,
+    LatinAmericanSpanish,
+    Italian,
+    Korean,
+    ChineseTraditional,
+    ChineseSimplified,
+    Portuguese,
+    Polish,
+    Russian,
+    Danish,
+    Finnish,
+    Dutch,
+    Norwegian,
+}
+
+const LANGUAGES: [Language; 17] = [
+    Language::English,
+    Language::Japanese,
+    Language::German,
+    Language::French,
+    Language::Spanish,
+    Language::LatinAmericanSpanish,
+    Language::Italian,
+    Language::Korean,
+    Language::ChineseTraditional,
+    Language::ChineseSimplified,
+    Language::Portuguese,
+    Language::Polish,
+    Language::Russian,
+    Language::Danish,
+    Language::Finnish,
+    Language::Dutch,
+    Language::Norwegian,
+];
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x48))]
+struct StringReference {
+    #[data(offset(0x00))]
+    string_id: FieldStringId,
+    #[data(offset(0x04))]
+    english: FieldLongInteger,
+    #[data(offset(0x08))]
+    japanese: FieldLongInteger,
+    #[data(offset(0x0C))]
+    german: FieldLongInteger,
+    #[data(offset(0x10))]
+    french: FieldLongInteger,
+    #[data(offset(0x14))]
+    spanish: FieldLongInteger,
+    #[data(offset(0x18))]
+    latin_american_spanish: FieldLongInteger,
+    #[data(offset(0x1C))]
+    italian: FieldLongInteger,
+    #[data(offset(0x20))]
+    korean: FieldLongInteger,
+    #[data(offset(0x24))]
+    chinese_traditional: FieldLongInteger,
+    #[data(offset(0x28))]
+    chinese_simplified: FieldLongInteger,
+    #[data(offset(0x2C))]
+    portuguese: FieldLongInteger,
+    #[data(offset(0x30))]
+    polish: FieldLongInteger,
+    #[data(offset(0x34))]
+    russian: FieldLongInteger,
+    #[data(offset(0x38))]
+    danish: FieldLongInteger,
+    #[data(offset(0x3C))]
+    finnish: FieldLongInteger,
+    #[data(offset(0x40))]
+    dutch: FieldLongInteger,
+    #[data(offset(0x44))]
+    norwegian: FieldLongInteger,
+}
+
+impl StringReference {
+    /// Offset of the string in the string data blob for the given language, -1 if not present.
+    fn offset(&self, language: Language) -> i32 {
+        [
+            &self.english,
+            &self.japanese,
+            &self.german,
+            &self.french,
+            &self.spanish,
+            &self.latin_american_spanish,
+            &self.italian,
+            &self.korean,
+            &self.chinese_traditional,
+            &self.chinese_simplified,
+            &self.portuguese,
+            &self.polish,
+            &self.russian,
+            &self.danish,
+            &self.finnish,
+            &self.dutch,
+            &self.norwegian,
+        ][language as usize]
+            .0
+    }
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x3C))]
+struct MultilingualUnicodeStringListTag {
+    #[data(offset(0x00))]
+    any_tag: AnyTag,
+    #[data(offset(0x10))]
+    string_references: FieldBlock<StringReference>,
+    #[data(offset(0x24))]
+    string_data: FieldData,
+}
+
+/// Encoding of the strings in the string data blob of a string list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    Utf16,
+}
+
+/// String list with its string data blob.
+struct StringTable {
+    tag: MultilingualUnicodeStringListTag,
+    data: Vec<u8>,
+    encoding: Encoding,
+}
+
+impl StringTable {
+    /// Creates a string table, detecting the encoding of the string data blob.
+    ///
+    /// Strings are usually stored as NUL-terminated UTF-8, but NUL-terminated UTF-16LE blobs are handled as well.
+    /// The first English string is used to tell them apart: in UTF-16, its first (ASCII) character is followed
+    /// by a zero byte.
+    fn new(tag: MultilingualUnicodeStringListTag, data: Vec<u8>) -> Self {
+        let first = tag
+            .string_references
+            .elements
+            .iter()
+            .find_map(|reference| usize::try_from(reference.offset(Language::English)).ok());
+        let encoding = match first.and_then(|offset| data.get(offset..offset + 2)) {
+            Some([low, 0]) if *low != 0 => Encoding::Utf16,
+            _ => Encoding::Utf8,
+        };
+        Self {
+            tag,
+            data,
+            encoding,
+        }
+    }
+
+    /// Gets the English string for the given string id.
+    fn get(&self, string_id: u32) -> Option<Cow<'_, str>> {
+        self.get_localized(string_id, Language::English)
+    }
+
+    /// Gets the string for the given string id in the specified language.
+    fn get_localized(&self, string_id: u32, language: Language) -> Option<Cow<'_, str>> {
+        let reference = self
+            .tag
+            .string_references
+            .elements
+            .iter()
+            .find(|reference| reference.string_id.0 as u32 == string_id)?;
+        let offset = usize::try_from(reference.offset(language)).ok()?;
+        let data = self.data.get(offset..)?;
+        match self.encoding {
+            Encoding::Utf8 => {
+                let string = CStr::from_bytes_until_nul(data).ok()?;
+                string.to_str().ok().map(Cow::Borrowed)
+            }
+            Encoding::Utf16 => {
+                let units: Vec<u16> = data
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                    .take_while(|&unit| unit != 0)
+                    .collect();
+                String::from_utf16(&units).ok().map(Cow::Owned)
+            }
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let mut module = ModuleFile::from_path(MODULE_PATH)?;
+    for idx in 0..module.files.len() {
+        if module.files[idx].tag_group != STRING_LIST_GROUP {
+            continue;
+        }
+        let Some(tag) = module.read_tag(idx as u32)? else {
+            continue;
+        };
+        let mut string_list = MultilingualUnicodeStringListTag::default();
+        tag.read_metadata(&mut string_list)?;
+
+        // The string data blob is stored in the datablock pointed to by the data reference of `string_data`.
+        let tag_info = tag
+            .tag_info
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NoTagInfo))?;
+        let main_struct = tag_info
+            .main_struct()
+            .ok_or(Error::TagError(TagError::MainStructNotFound))?;
+        let buffer = tag
+            .data_stream
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NotLoaded))?
+            .get_ref()
+            .get_ref();
+        let data = tag_info
+            .data_references
+            .iter()
+            .position(|reference| {
+                reference.field_block == main_struct.target_index && reference.field_offset == 0x24
+            })
+            .and_then(|ref_index| tag_info.read_data_reference(ref_index, buffer))
+            .unwrap_or_default()
+            .to_vec();
+
+        let table = StringTable::new(string_list, data);
+        for reference in &table.tag.string_references.elements {
+            let string_id = reference.string_id.0 as u32;
+            if let Some(string) = table.get(string_id) {
+                println!("{string_id:08X}: {string}");
+            }
+            for language in &LANGUAGES[1..] {
+                if let Some(string) = table.get_localized(string_id, *language) {
+                    println!("{string_id:08X} ({language:?}): {string}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a string table whose English strings are at the given offsets of `data`, without any Japanese strings.
+    fn table(offsets: &[i32], data: Vec<u8>) -> StringTable {
+        let mut tag = MultilingualUnicodeStringListTag::default();
+        tag.string_references.elements = offsets
+            .iter()
+            .zip(1..)
+            .map(|(&offset, string_id)| StringReference {
+                string_id: FieldStringId(string_id),
+                english: FieldLongInteger(offset),
+                japanese: FieldLongInteger(-1),
+                ..Default::default()
+            })
+            .collect();
+        StringTable::new(tag, data)
+    }
+
+    /// Counts the strings of the table present in the specified language.
+    fn count(table: &StringTable, language: Language) -> usize {
+        (1..=table.tag.string_references.elements.len() as u32)
+            .filter(|&string_id| table.get_localized(string_id, language).is_some())
+            .count()
+    }
+
+    #[test]
+    /// Verifies that UTF-8 strings are read, skipping references without a string in the language.
+    fn test_utf8_strings() {
+        let table = table(&[0, 6, -1], b"Hello\0World\0".to_vec());
+        assert_eq!(table.encoding, Encoding::Utf8);
+        assert_eq!(count(&table, Language::English), 2);
+        assert_eq!(table.get(2).as_deref(), Some("World"));
+        assert_eq!(table.get(3), None);
+        assert_eq!(count(&table, Language::Japanese), 0);
+    }
+
+    #[test]
+    /// Verifies that UTF-16 strings are detected and read.
+    fn test_utf16_strings() {
+        let data: Vec<u8> = "Hi\0Gr\u{FC}\u{DF}e\0"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let table = table(&[0, 6], data);
+        assert_eq!(table.encoding, Encoding::Utf16);
+        assert_eq!(count(&table, Language::English), 2);
+        assert_eq!(table.get(1).as_deref(), Some("Hi"));
+        assert_eq!(table.get(2).as_deref(), Some("Gr\u{FC}\u{DF}e"));
+    }
+}
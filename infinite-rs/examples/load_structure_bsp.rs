@@ -0,0 +1,181 @@
+use infinite_rs::module::set::ModuleSet;
+use infinite_rs::tag::types::common_types::{
+    AnyTag, FieldBlock, FieldRealBounds, FieldShortInteger, FieldTagResource,
+};
+use infinite_rs::Result;
+use infinite_rs_derive::TagStructure;
+
+const DEPLOY_PATH: &str = "C:/XboxGames/Halo Infinite/Content/deploy/";
+const STRUCTURE_BSP_GROUP: &str = "sbsp";
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x88))]
+/// Cluster of the BSP, which is the unit of visibility and rendering of level geometry.
+struct StructureBspCluster {
+    #[data(offset(0x00))]
+    bounds_x: FieldRealBounds,
+    #[data(offset(0x08))]
+    bounds_y: FieldRealBounds,
+    #[data(offset(0x10))]
+    bounds_z: FieldRealBounds,
+    /// Index of the mesh of the cluster in the render geometry.
+    #[data(offset(0x1C))]
+    mesh_index: FieldShortInteger,
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x48))]
+/// Placement of a shared piece of geometry in the BSP.
+struct StructureBspInstancedGeometryInstance {
+    /// Index of the definition of the geometry in the render geometry.
+    #[data(offset(0x04))]
+    definition_index: FieldShortInteger,
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x5A8))]
+struct StructureBspTag {
+    #[data(offset(0x00))]
+    any_tag: AnyTag,
+    #[data(offset(0x1C8))]
+    clusters: FieldBlock<StructureBspCluster>,
+    #[data(offset(0x2E0))]
+    instanced_geometry_instances: FieldBlock<StructureBspInstancedGeometryInstance>,
+    /// Resource containing the vertex and index buffers of the render geometry.
+    #[data(offset(0x500))]
+    render_geometry_resource: FieldTagResource,
+}
+
+fn main() -> Result<()> {
+    let mut modules = ModuleSet::from_deploy(&[DEPLOY_PATH])?;
+
+    for module in &mut modules.modules {
+        let bsps = module
+            .iter_metadata::<StructureBspTag>(STRUCTURE_BSP_GROUP)
+            .collect::<Result<Vec<_>>>()?;
+        for (index, bsp) in bsps {
+            let meshes = bsp
+                .clusters
+                .elements
+                .iter()
+                .filter(|cluster| cluster.mesh_index.0 != -1)
+                .count();
+            println!(
+                "{index}: {} clusters ({meshes} with meshes), {} instances",
+                bsp.clusters.elements.len(),
+                bsp.instanced_geometry_instances.elements.len(),
+            );
+            // The vertex and index buffers are only located, not decoded.
+            if let Some(resource) =
+                module.resolve_tag_resource(index as u32, &bsp.render_geometry_resource)
+            {
+                let resource = &module.files[resource];
+                println!(
+                    "    render geometry: {} ({} bytes)",
+                    resource.tag_name, resource.total_uncompressed_size
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use infinite_rs::module::file::ModuleFileEntry;
+    use infinite_rs::tag::datablock::TagSectionType;
+
+    /// Size in bytes of the header of the tag, with three datablocks and three structs.
+    const HEADER_SIZE: u32 = 0xE0;
+
+    /// Builds a structure BSP tag with a cluster for each mesh index and `instances` geometry instances.
+    fn synthetic_bsp(mesh_indices: &[i16], instances: u32) -> Vec<u8> {
+        let cluster_count = u32::try_from(mesh_indices.len()).unwrap();
+        let mut main = vec![0; 0x5A8];
+        main[0x1D8..0x1DC].copy_from_slice(&cluster_count.to_le_bytes());
+        main[0x2F0..0x2F4].copy_from_slice(&instances.to_le_bytes());
+        let mut clusters = vec![0; 0x88 * mesh_indices.len()];
+        for (cluster, mesh_index) in clusters.chunks_mut(0x88).zip(mesh_indices) {
+            cluster[0x1C..0x1E].copy_from_slice(&mesh_index.to_le_bytes());
+        }
+        let geometry = vec![0; 0x48 * instances as usize];
+        let blocks = [&main, &clusters, &geometry];
+        let data_size: usize = blocks.iter().map(|block| block.len()).sum();
+
+        let mut tag = Vec::new();
+        // Header
+        tag.extend_from_slice(&0x6873_6375u32.to_le_bytes()); // magic
+        tag.extend_from_slice(&27i32.to_le_bytes()); // version
+        tag.extend_from_slice(&[0; 16]); // root struct guid and checksum
+        for count in [0u32, 3, 3, 0, 0] {
+            // dependencies, datablocks, structs, data references, tag references
+            tag.extend_from_slice(&count.to_le_bytes());
+        }
+        tag.extend_from_slice(&[0; 12]); // string table size, zoneset size, unknown
+        for size in [HEADER_SIZE, u32::try_from(data_size).unwrap(), 0, 0] {
+            // header, data, resource and actual resource sizes
+            tag.extend_from_slice(&size.to_le_bytes());
+        }
+        tag.extend_from_slice(&[0; 8]); // alignments and resource flag
+
+        // Datablocks
+        let mut offset = 0u64;
+        for block in blocks {
+            tag.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+            tag.extend_from_slice(&0u16.to_le_bytes()); // padding
+            tag.extend_from_slice(&(TagSectionType::TagData as u16).to_le_bytes());
+            tag.extend_from_slice(&offset.to_le_bytes());
+            offset += block.len() as u64;
+        }
+
+        // Structs: the main struct, then the cluster and instance blocks it points to.
+        for (struct_type, target_index, field_block, field_offset) in [
+            (0u16, 0i32, -1i32, 0u32),
+            (1, 1, 0, 0x1C8),
+            (1, 2, 0, 0x2E0),
+        ] {
+            tag.extend_from_slice(&[0; 16]); // guid
+            tag.extend_from_slice(&struct_type.to_le_bytes());
+            tag.extend_from_slice(&0u16.to_le_bytes()); // unknown
+            tag.extend_from_slice(&target_index.to_le_bytes());
+            tag.extend_from_slice(&field_block.to_le_bytes());
+            tag.extend_from_slice(&field_offset.to_le_bytes());
+        }
+        assert_eq!(tag.len(), HEADER_SIZE as usize);
+        for block in blocks {
+            tag.extend_from_slice(block);
+        }
+        tag
+    }
+
+    #[test]
+    /// Verifies that the clusters and geometry instances of the BSP are read from their blocks.
+    fn test_structure_bsp_counts() -> Result<()> {
+        let mut entry =
+            ModuleFileEntry::from_bytes(STRUCTURE_BSP_GROUP, synthetic_bsp(&[0, -1, 1], 2))?;
+        let mut bsp = StructureBspTag::default();
+        entry.read_metadata(&mut bsp)?;
+
+        let mesh_indices: Vec<i16> = bsp
+            .clusters
+            .elements
+            .iter()
+            .map(|cluster| cluster.mesh_index.0)
+            .collect();
+        assert_eq!(mesh_indices, [0, -1, 1]);
+        assert_eq!(bsp.instanced_geometry_instances.elements.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    /// Verifies that a BSP without clusters or geometry instances is read as empty.
+    fn test_empty_structure_bsp() -> Result<()> {
+        let mut entry = ModuleFileEntry::from_bytes(STRUCTURE_BSP_GROUP, synthetic_bsp(&[], 0))?;
+        let mut bsp = StructureBspTag::default();
+        entry.read_metadata(&mut bsp)?;
+        assert!(bsp.clusters.elements.is_empty());
+        assert!(bsp.instanced_geometry_instances.elements.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+//! Feeds arbitrary bytes to [`ModuleFile::read_from_slice`], which reads the module header, file entries,
+//! string table and blocks, then reads every tag with [`ModuleFile::read_tag`]. Reading must return an error
+//! instead of panicking, and counts read from the header must not cause large allocations.
+//!
+//! Run with `cargo +nightly fuzz run fuzz_module -- -rss_limit_mb=512` from the `infinite-rs` folder.
+//! The memory limit makes the fuzzer fail on unbounded allocations.
+
+#![no_main]
+
+use infinite_rs::ModuleFile;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut module = ModuleFile::default();
+    if module.read_from_slice(data.to_vec()).is_err() {
+        return;
+    }
+    for index in 0..module.files.len() {
+        let _ = module.read_tag(u32::try_from(index).unwrap());
+    }
+});
@@ -0,0 +1,16 @@
+//! Feeds arbitrary bytes to [`TagFile::from_bytes`], which must return an error instead of panicking.
+//!
+//! Run with `cargo +nightly fuzz run fuzz_tag` from the `infinite-rs` folder (requires `cargo install cargo-fuzz`).
+
+#![no_main]
+
+use infinite_rs::module::header::ModuleVersion;
+use infinite_rs::TagFile;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Tag names are only read from the string table before Season 3, so both paths are covered.
+    for version in [ModuleVersion::Release, ModuleVersion::Season3] {
+        let _ = TagFile::from_bytes(data, &version);
+    }
+});
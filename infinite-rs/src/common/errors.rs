@@ -4,7 +4,7 @@ use num_enum::TryFromPrimitiveError;
 use std::io::Error as StdIoError;
 use std::num::TryFromIntError;
 use std::result::Result as StdResult;
-use std::string::FromUtf8Error;
+use std::string::{FromUtf16Error, FromUtf8Error};
 use thiserror::Error;
 
 use crate::{
@@ -26,6 +26,21 @@ pub enum ModuleError {
     /// This error serves as a runtime assert.
     #[error("Module file block index must be non-negative, found {0}")]
     NegativeBlockIndex(i32),
+    /// Parent or resource index of a file entry points outside of the module, indicating file corruption.
+    #[error("Index in module file points outside of the module!")]
+    InvalidIndex,
+    /// Data of the file is stored in an HD1 file that has not been loaded, or in a debug module which is not supported.
+    #[error("File data is not available!")]
+    DataNotAvailable,
+    /// Data failed to decompress and does not start with a valid Kraken header, which usually means that the
+    /// module is encrypted or otherwise protected. Such modules are not supported.
+    /// Contains the error code returned by the Kraken decompressor.
+    #[error("Failed to decompress data (error code {0}), the module may be encrypted or protected, which is not supported!")]
+    PossiblyEncrypted(i32),
+    /// Decompressed ranges of the blocks making up a file overlap or exceed the size of the file, indicating file corruption.
+    /// Only checked when blocks are decompressed in parallel.
+    #[error("Blocks of the file overlap or exceed the decompressed size of the file!")]
+    OverlappingBlocks,
 }
 
 #[derive(Error, Debug)]
@@ -44,6 +59,10 @@ pub enum TagError {
     /// Main struct designated by [`MainStruct`](`crate::tag::structure::TagStructType`) was not found in tag file.
     #[error("Main struct not found!")]
     MainStructNotFound,
+    /// No struct definition of the tag has the requested GUID.
+    /// Contains the GUID that was searched for.
+    #[error("Struct with GUID {0:#X} not found!")]
+    StructNotFound(u128),
     /// Tag metadata headers [`tag_info`](`crate::module::file::ModuleFileEntry::tag_info`) are missing.
     /// This occurs when attempting to read metadata from a [`RawFile`](`crate::module::file::FileEntryFlags::RAW_FILE`).
     #[error("Does not contain tag info!")]
@@ -59,6 +78,42 @@ pub enum TagError {
     /// Failed to convert primitive to enum in [`common_types`](`crate::tag::types::common_types`).
     #[error("Failed to convert primitive to enum")]
     NumEnumError,
+    /// Failed to read a field of a [`TagStructure`](`crate::module::file::TagStructure`).
+    /// Contains the name of the field and the absolute offset it was read from, which helps pinpointing incorrect struct definitions.
+    #[error("Failed to read field `{field}` at offset {offset:#X}!")]
+    FieldReadError {
+        /// Name of the field that failed to be read.
+        field: &'static str,
+        /// Offset in the tag data where the field was read from.
+        offset: u64,
+        /// Error that occurred while reading the field.
+        source: Box<Error>,
+    },
+    /// A data reference or datablock points outside of the loaded tag data.
+    #[error("Data reference points outside of the tag data!")]
+    DataOutOfBounds,
+    /// The size of the tag header in the file entry is larger than the total size of the file, indicating file corruption.
+    /// Contains the header size and the total size.
+    #[error("Tag header size {0:#X} is larger than the file size {1:#X}!")]
+    InvalidHeaderSize(u32, u32),
+    /// The tables of the tag header (dependencies, datablocks, structs, references, string table and zonesets) extend
+    /// past the [`header_size`](`crate::tag::header::TagHeader::header_size`), indicating corrupted counts in the header.
+    /// Only returned by [`TagFile::validate`](`crate::tag::loader::TagFile::validate`).
+    /// Contains the end offset of the tables and the header size.
+    #[error("Tag header tables end at {0:#X}, past the header size {1:#X}!")]
+    TableOverrun(u64, u32),
+    /// A tag struct points to a datablock that does not exist, for instance in tags without any datablocks.
+    #[error("Datablock index {0} is out of range!")]
+    BlockIndexOutOfRange(i32),
+    /// The resource sections of the file were not loaded, as it was read with
+    /// [`defer_resources`](`crate::module::builder::ModuleFileBuilder::defer_resources`).
+    /// Use [`load_resources`](`crate::ModuleFile::load_resources`) to load them.
+    #[error("Resource sections have not been loaded yet!")]
+    ResourcesDeferred,
+    /// Verifying the [`checksum`](`crate::tag::header::TagHeader::checksum`) of a tag is not supported, as the algorithm used
+    /// to generate it is unknown.
+    #[error("Tag checksum verification is not supported!")]
+    ChecksumUnsupported,
     /// Recursion depth reached 3 when trying to get tag path.
     /// This should never ever happen, if it has, something has gone very wrong.
     #[error("Recursion depth reached 3!")]
@@ -84,9 +139,12 @@ pub enum Error {
     /// IO error from [`std::io`] operations.
     #[error("Failed to read from buffer!")]
     ReadError(#[from] StdIoError),
-    /// UTF-8 decoding error in [`read_fixed_string`](`crate::common::extensions::BufReaderExt::read_fixed_string`).
+    /// UTF-8 decoding error in [`read_fixed_string`](`crate::common::extensions::BufReaderExt::read_fixed_string`) or [`FourCc::read`](`crate::tag::group::FourCc::read`).
     #[error("Incorrect UTF-8 encoding found when reading string!")]
     Utf8ReadingError(#[from] FromUtf8Error),
+    /// UTF-16 decoding error in [`read_fixed_string_utf16le`](`crate::common::extensions::BufReaderExt::read_fixed_string_utf16le`).
+    #[error("Incorrect UTF-16 encoding found when reading string!")]
+    Utf16ReadingError(#[from] FromUtf16Error),
     /// Kraken decompression error.
     #[error("Error occurred while decompressing!")]
     DecompressionError(#[from] DecompressionError),
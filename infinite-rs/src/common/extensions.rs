@@ -11,15 +11,25 @@
 //! These extensions are implemented as traits and require the reader to implement both
 //! [`Read`] and [`Seek`] traits.
 //!
+//! Besides [`BufReader`], the extensions are also implemented for [`Cursor`], which allows tag structures and
+//! field types to be read directly from an in-memory byte slice using [`ByteReader`], without any file access.
+//!
+//! Other readers can implement [`BufReaderExt`] as well, as every method has a default implementation. Skipping data
+//! uses [`Seek::seek_relative`], which falls back to [`Seek::seek`] for readers that do not implement it natively.
+//!
 
-use std::io::{BufRead, BufReader, Read, Seek};
+use std::io::{BufRead, BufReader, Cursor, Read, Seek};
 
 use crate::Result;
 
+/// Maximum number of items preallocated by [`read_enumerable`](`BufReaderExt::read_enumerable`).
+/// Larger counts still work, but grow the vector as items are read, so that corrupted counts fail on reading instead of allocating.
+const MAX_PREALLOCATED_ENUMERABLES: usize = 0x1000;
+
 /// Trait for types that can be read sequentially from a buffered reader.
 ///
 /// Types implementing this trait can be read using the [`read_enumerable`](`BufReaderExt::read_enumerable`) method
-/// from [`BufReaderExt`].
+/// from [`BufReaderExt`]. This is the canonical name of the trait, which was called [`Readable`] before 0.5.0.
 pub trait Enumerable {
     /// Reads data from the given reader and updates the implementing type.
     ///
@@ -32,6 +42,30 @@ pub trait Enumerable {
     fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()>;
 }
 
+#[deprecated(
+    note = "renamed to `Enumerable`, which is implemented for every type implementing `Readable`"
+)]
+/// Former name of [`Enumerable`], kept so that types implementing it can still be read with
+/// [`read_enumerable`](`BufReaderExt::read_enumerable`).
+pub trait Readable {
+    /// Reads data from the given reader and updates the implementing type.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A mutable reference to any type that implements `BufReaderExt`
+    ///
+    /// # Errors
+    /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()>;
+}
+
+#[allow(deprecated)]
+impl<T: Readable> Enumerable for T {
+    fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+        Readable::read(self, reader)
+    }
+}
+
 /// Extension trait for [`BufRead`] to add custom reading methods.
 pub trait BufReaderExt: BufRead + Seek {
     /// Reads a fixed-length UTF-8 encoded string from the reader.
@@ -72,6 +106,42 @@ pub trait BufReaderExt: BufRead + Seek {
         Ok(string)
     }
 
+    /// Reads a fixed-length UTF-16 (little endian) encoded string from the reader.
+    ///
+    /// This function reads exactly `char_count` UTF-16 code units (`char_count * 2` bytes) and converts them to a String.
+    /// Null characters are kept, as with [`read_fixed_string`](`BufReaderExt::read_fixed_string`).
+    ///
+    /// # Arguments
+    ///
+    /// * `char_count` - The exact number of UTF-16 code units to read
+    ///
+    /// # Errors
+    /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    /// - If the code units read are not valid UTF-16 [`Utf16ReadingError`](`crate::Error::Utf16ReadingError`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use infinite_rs::common::extensions::{BufReaderExt, ByteReader};
+    ///
+    /// let data = [0x48, 0x00, 0x69, 0x00];
+    /// let mut reader = ByteReader::new(&data[..]);
+    /// let string = reader.read_fixed_string_utf16le(2).unwrap();
+    /// assert_eq!(string, "Hi");
+    /// ```
+    fn read_fixed_string_utf16le(&mut self, char_count: usize) -> Result<String> {
+        let mut buffer = vec![0; char_count * 2];
+        self.read_exact(&mut buffer)?;
+
+        let units: Vec<u16> = buffer
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        let string = String::from_utf16(&units)?;
+
+        Ok(string)
+    }
+
     /// Reads a null-terminated string from the reader.
     ///
     /// This function reads bytes in a reader until it hits `0x00` and converts them to a String.
@@ -153,8 +223,9 @@ pub trait BufReaderExt: BufRead + Seek {
         Self: Sized,
         Vec<T>: FromIterator<T>,
     {
-        let mut enumerables = vec![];
-        enumerables.reserve_exact(usize::try_from(count)? + 1);
+        // Counts are read from the file, so they are not trusted for preallocation.
+        let mut enumerables =
+            Vec::with_capacity(usize::try_from(count)?.min(MAX_PREALLOCATED_ENUMERABLES));
         for _ in 0..count {
             let mut enumerable = T::default();
             enumerable.read(self)?;
@@ -165,11 +236,28 @@ pub trait BufReaderExt: BufRead + Seek {
 }
 
 impl<R: Read + Seek> BufReaderExt for BufReader<R> {}
+impl<T: AsRef<[u8]>> BufReaderExt for Cursor<T> {}
+
+/// Reader over an in-memory byte slice.
+///
+/// Useful for parsing tag structures from data that has already been extracted, as it does not require
+/// a file or a [`BufReader`].
+///
+/// # Examples
+///
+/// ```
+/// use infinite_rs::common::extensions::{BufReaderExt, ByteReader};
+///
+/// let data = b"mat \x00";
+/// let mut reader = ByteReader::new(&data[..]);
+/// assert_eq!(reader.read_null_terminated_string().unwrap(), "mat ");
+/// ```
+pub type ByteReader<'a> = Cursor<&'a [u8]>;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Cursor;
+    use std::io::{Cursor, Result as IoResult, SeekFrom};
 
     #[test]
     /// Verifies that reading 0xFFFFFFFF returns an empty string, which is used
@@ -180,4 +268,100 @@ mod tests {
         let string = reader.read_fixed_string(data.len()).unwrap();
         assert_eq!(string, "");
     }
+
+    /// Reader over a byte vector that only implements the required methods of [`BufRead`] and [`Seek`].
+    struct MinimalReader {
+        data: Vec<u8>,
+        position: usize,
+    }
+
+    impl Read for MinimalReader {
+        fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+            let length = self.fill_buf()?.read(buf)?;
+            self.consume(length);
+            Ok(length)
+        }
+    }
+
+    impl BufRead for MinimalReader {
+        fn fill_buf(&mut self) -> IoResult<&[u8]> {
+            Ok(self.data.get(self.position..).unwrap_or_default())
+        }
+
+        fn consume(&mut self, amt: usize) {
+            self.position += amt;
+        }
+    }
+
+    impl Seek for MinimalReader {
+        fn seek(&mut self, pos: SeekFrom) -> IoResult<u64> {
+            let position = match pos {
+                SeekFrom::Start(offset) => i64::try_from(offset).unwrap(),
+                SeekFrom::End(offset) => i64::try_from(self.data.len()).unwrap() + offset,
+                SeekFrom::Current(offset) => i64::try_from(self.position).unwrap() + offset,
+            };
+            self.position = usize::try_from(position).unwrap();
+            Ok(u64::try_from(position).unwrap())
+        }
+    }
+
+    impl BufReaderExt for MinimalReader {}
+
+    #[test]
+    /// Verifies that readers only implementing [`Seek::seek`] can skip padding and read fields.
+    fn test_minimal_reader() {
+        use crate::tag::types::common_types::FieldPad;
+
+        let mut reader = MinimalReader {
+            data: b"\xFF\xFF\xFF\xFFmat \x00".to_vec(),
+            position: 0,
+        };
+        FieldPad.read(&mut reader, 4).unwrap();
+        assert_eq!(reader.read_null_terminated_string().unwrap(), "mat ");
+        assert_eq!(reader.stream_position().unwrap(), 9);
+    }
+
+    #[derive(Default)]
+    struct LegacyType {
+        value: u8,
+    }
+
+    #[allow(deprecated)]
+    impl Readable for LegacyType {
+        fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+            let mut buffer = [0];
+            reader.read_exact(&mut buffer)?;
+            self.value = buffer[0];
+            Ok(())
+        }
+    }
+
+    #[test]
+    /// Verifies that types implementing the deprecated `Readable` trait can be read with `read_enumerable`.
+    fn test_readable_bridge() {
+        let mut reader = ByteReader::new(&[1, 2, 3][..]);
+        let values = reader.read_enumerable::<LegacyType>(3).unwrap();
+        assert_eq!(
+            values.iter().map(|value| value.value).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+    }
+
+    #[test]
+    /// Verifies that field types can be read from a plain byte slice.
+    fn test_byte_reader_fields() {
+        use crate::tag::types::common_types::{FieldLongInteger, FieldReference};
+
+        let mut data = [0u8; 0x20];
+        data[0x14..0x18].copy_from_slice(b"mtib");
+        data[0x1C..0x20].copy_from_slice(&42i32.to_le_bytes());
+        let mut reader = ByteReader::new(&data[..]);
+
+        let mut reference = FieldReference::default();
+        reference.read(&mut reader).unwrap();
+        let mut integer = FieldLongInteger::default();
+        integer.read(&mut reader).unwrap();
+        assert_eq!(reference.group, "bitm");
+        assert_eq!(integer.0, 42);
+    }
 }
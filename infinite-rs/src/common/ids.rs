@@ -0,0 +1,166 @@
+//! Typed handles for tag IDs and file indices.
+//!
+//! Both are plain integers in the module format, and are easy to confuse when passed around. Functions taking
+//! these types instead of bare integers make passing an index where an ID is expected (and vice versa) a compile
+//! time error.
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Global ID of a tag, as stored in [`tag_id`](`crate::module::file::ModuleFileEntry::tag_id`).
+///
+/// This is a 32 bit hash of the tag path, and is the same across every module that contains the tag.
+pub struct TagId(pub i32);
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Index of a file in the [`files`](`crate::ModuleFile::files`) of a module.
+///
+/// Unlike [`TagId`], this is only meaningful for the module the file was read from.
+pub struct FileIndex(pub usize);
+
+impl TagId {
+    /// Value used for files that are not tags, such as resources.
+    pub const NONE: Self = Self(-1);
+
+    /// Computes the tag ID of a tag path.
+    ///
+    /// The path is hashed as given, so it needs to be written exactly the way the game hashes it (as stored in
+    /// [`tag_name`](`crate::module::file::ModuleFileEntry::tag_name`) of modules that contain a string table),
+    /// otherwise the ID will not match any tag.
+    ///
+    /// **Experimental:** the ID is taken as the lower 32 bits of the `MurmurHash3_x64_128` hash of the path (with a
+    /// seed of 0). This has only been checked against the reference implementation of the hash, not against IDs of
+    /// tags shipped with the game, so prefer looking up IDs read from modules where possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the tag, including its extension.
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        let (low, _) = murmur3_x64_128(path.as_bytes(), 0);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let tag_id = low as u32 as i32;
+        Self(tag_id)
+    }
+}
+
+/// Final mix of a 64 bit `MurmurHash3` lane, forcing all bits to avalanche.
+const fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    k ^= k >> 33;
+    k
+}
+
+/// `MurmurHash3_x64_128` hash of the given data, returned as its two 64 bit halves.
+fn murmur3_x64_128(data: &[u8], seed: u64) -> (u64, u64) {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+
+    let mut h1 = seed;
+    let mut h2 = seed;
+    let lane = |bytes: &[u8]| {
+        let mut buffer = [0u8; 8];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        u64::from_le_bytes(buffer)
+    };
+
+    let mut chunks = data.chunks_exact(16);
+    for chunk in &mut chunks {
+        let k1 = lane(&chunk[..8]);
+        let k2 = lane(&chunk[8..]);
+
+        h1 ^= k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dc_e729);
+        h2 ^= k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x3849_5ab5);
+    }
+
+    let tail = chunks.remainder();
+    if tail.len() > 8 {
+        h2 ^= lane(&tail[8..])
+            .wrapping_mul(C2)
+            .rotate_left(33)
+            .wrapping_mul(C1);
+    }
+    if !tail.is_empty() {
+        h1 ^= lane(&tail[..tail.len().min(8)])
+            .wrapping_mul(C1)
+            .rotate_left(31)
+            .wrapping_mul(C2);
+    }
+
+    let length = data.len() as u64;
+    h1 ^= length;
+    h2 ^= length;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix64(h1);
+    h2 = fmix64(h2);
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    (h1, h2)
+}
+
+impl From<i32> for TagId {
+    fn from(value: i32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<TagId> for i32 {
+    fn from(value: TagId) -> Self {
+        value.0
+    }
+}
+
+impl From<usize> for FileIndex {
+    fn from(value: usize) -> Self {
+        Self(value)
+    }
+}
+
+impl From<FileIndex> for usize {
+    fn from(value: FileIndex) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Verifies that both handles convert to and from their underlying integers.
+    fn test_conversions() {
+        let tag_id = TagId::from(-0x1234);
+        assert_eq!(i32::from(tag_id), -0x1234);
+        assert_eq!(TagId::from(-1), TagId::NONE);
+
+        let index = FileIndex::from(7);
+        assert_eq!(usize::from(index), 7);
+    }
+
+    #[test]
+    /// Verifies the hash against the reference implementation, and that tag IDs are its lower 32 bits.
+    fn test_from_path() {
+        assert_eq!(murmur3_x64_128(b"", 0), (0, 0));
+        assert_eq!(
+            murmur3_x64_128(b"The quick brown fox jumps over the lazy dog", 0),
+            (0xe34b_bc7b_bc07_1b6c, 0x7a43_3ca9_c49a_9347)
+        );
+        assert_eq!(
+            TagId::from_path("The quick brown fox jumps over the lazy dog"),
+            TagId(-0x43f8_e494)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! Logging helpers used throughout the crate.
+//!
+//! Events are only emitted if the `tracing` feature is enabled. Otherwise, the macros expand to nothing and their
+//! arguments are not evaluated, so logging has no cost.
+
+/// Emits a debug event using [`tracing`](https://docs.rs/tracing) if the `tracing` feature is enabled.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+/// Emits a warning event using [`tracing`](https://docs.rs/tracing) if the `tracing` feature is enabled.
+macro_rules! warn_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)*);
+    };
+}
+
+pub(crate) use debug;
+// `warn` is also the name of a built-in attribute, so the macro is defined under another name.
+pub(crate) use warn_event as warn;
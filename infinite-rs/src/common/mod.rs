@@ -2,3 +2,5 @@
 
 pub mod errors;
 pub mod extensions;
+pub mod ids;
+pub(crate) mod logging;
@@ -31,7 +31,8 @@ fn load_modules() -> Result<()> {
 ## Loading a tag file
 After we have loaded a module file, we can now use the [`read_tag`](`ModuleFile::read_tag`) function to load a specific tag by index from the module file. This populates the [`data_stream`](`crate::module::file::ModuleFileEntry::data_stream`) and [`tag_info`](`crate::module::file::ModuleFileEntry::tag_info`) properties in a module entry that we can use later.
 
-The [`read_tag_from_id`](`ModuleFile::read_tag_from_id`) function is also available to load a tag by its global ID.
+The [`read_tag_from_id`](`ModuleFile::read_tag_from_id`) function is also available to load a tag by its global ID, wrapped in a [`TagId`].
+If only the parsed tag file is needed, [`read_tag_info`](`ModuleFile::read_tag_info`) returns it directly.
 
 ```rust
 use infinite_rs::{ModuleFile, Result};
@@ -59,6 +60,8 @@ To define a structure that can be read from a tag data stream, you must first de
 
 *Padding between fields is automatically calculated. Any data between two offsets are skipped.*
 
+Fields of type [`FieldBlock`](`crate::tag::types::common_types::FieldBlock`) are loaded recursively. Blocks are detected by the name of their type, so a block hidden behind a type alias must be marked with `#[data(offset(...), block)]` to be loaded.
+
 ```rust
 use infinite_rs_derive::TagStructure;
 use infinite_rs::tag::types::common_types::{
@@ -99,7 +102,7 @@ fn load_tags() -> Result<()> {
     // We now want to find the material tags in the module file.
     let material_indices = module.files.iter()
         .enumerate()
-        .filter(|(_, file)| file.tag_group == "mat ")
+        .filter(|(_, file)| file.is_group("mat"))
         .map(|(index, _)| index)
         .collect::<Vec<_>>();
 
@@ -120,6 +123,8 @@ fn load_tags() -> Result<()> {
 }
 ```
 
+Both steps can also be done in a single call with [`read_full`](`ModuleFile::read_full`), which returns [`None`] for tags that could not be loaded.
+
 ### Reading enums and flags
 `infinite-rs` also supports the usage of enums and flags as fields, available on the common types: `FieldCharEnum`, `FieldShortEnum`, `FieldLongEnum`, `FieldLongFlags`, `FieldWordFlags` and `FieldByteFlags`.
 
@@ -160,6 +165,15 @@ struct ExampleStruct {
 }
 ```
 
+## Logging
+When the `tracing` feature is enabled, `infinite-rs` emits [`tracing`](https://docs.rs/tracing) spans around reading tags, reading metadata
+and decompressing blocks, along with debug events containing the tag group, sizes and block counts of each file. With a subscriber
+such as `tracing-subscriber`, these can be enabled using `RUST_LOG=infinite_rs=debug`. When the feature is disabled, no logging code is compiled.
+
+## Parallel decompression
+Large tags can be split into many compressed blocks. When the `rayon` feature is enabled, the blocks of a single file are read
+sequentially and then decompressed in parallel using [`rayon`](https://docs.rs/rayon). Blocks are decompressed one after another by default.
+
 ## Credits
 - [libinfinite](https://github.com/Coreforge/libInfinite) by Coreforge, which this project is mostly based on.
 - [Reclaimer](https://github.com/Gravemind2401/Reclaimer) by Gravemind2401, which helped me get familiar with Blam file formats.
@@ -173,9 +187,12 @@ struct ExampleStruct {
 
 pub mod common;
 pub mod module;
+pub mod prelude;
 pub mod tag;
 
 #[doc(inline)]
 pub use crate::common::errors::{Error, Result};
 #[doc(inline)]
+pub use crate::common::ids::{FileIndex, TagId};
+#[doc(inline)]
 pub use crate::{module::loader::ModuleFile, tag::loader::TagFile};
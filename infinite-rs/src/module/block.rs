@@ -9,8 +9,9 @@ use crate::Result;
 #[derive(Default, Debug)]
 /// Represents a module block entry containing information related to Kraken compression.
 /// This struct is used to determine how to read bytes in [`ModuleFileEntry`](`super::file::ModuleFileEntry`).
-pub(super) struct ModuleBlockEntry {
-    /// Offset in bytes of compressed data inside the module (after [`file_data_offset`](`super::loader::ModuleFile::file_data_offset`) in the module).
+pub struct ModuleBlockEntry {
+    /// Offset in bytes of compressed data inside the module, relative to the start of the data of the file entry (found at
+    /// [`data_offset`](`super::file::ModuleFileEntry::data_offset`) after [`file_data_offset`](`super::loader::ModuleFile::file_data_offset`)).
     pub(super) compressed_offset: u32,
     /// Size in bytes of compressed data inside the module.
     pub(super) compressed_size: u32,
@@ -23,6 +24,38 @@ pub(super) struct ModuleBlockEntry {
     pub(super) is_compressed: bool,
 }
 
+impl ModuleBlockEntry {
+    /// Gets the offset in bytes of the compressed data inside the module, relative to the start of the file data.
+    #[must_use]
+    pub fn compressed_offset(&self) -> u32 {
+        self.compressed_offset
+    }
+
+    /// Gets the size in bytes of the compressed data inside the module.
+    #[must_use]
+    pub fn compressed_size(&self) -> u32 {
+        self.compressed_size
+    }
+
+    /// Gets the offset in bytes of the decompressed data inside the decompression buffer.
+    #[must_use]
+    pub fn decompressed_offset(&self) -> u32 {
+        self.decompressed_offset
+    }
+
+    /// Gets the size in bytes of the decompressed data.
+    #[must_use]
+    pub fn decompressed_size(&self) -> u32 {
+        self.decompressed_size
+    }
+
+    /// Gets whether the block is compressed with Kraken.
+    #[must_use]
+    pub fn is_compressed(&self) -> bool {
+        self.is_compressed
+    }
+}
+
 impl Enumerable for ModuleBlockEntry {
     fn read<R: BufRead>(&mut self, reader: &mut R) -> Result<()> {
         self.compressed_offset = reader.read_u32::<LE>()?;
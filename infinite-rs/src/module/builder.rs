@@ -0,0 +1,165 @@
+//! Builder for configuring how module files are read.
+
+use std::path::{Path, PathBuf};
+
+use super::loader::{ModuleFile, SharedHd1};
+use crate::Result;
+
+#[derive(Default, Debug)]
+/// Builder used to configure and open a [`ModuleFile`].
+///
+/// [`ModuleFile::from_path`] is equivalent to opening a module with the default builder, which:
+/// - Looks for the HD1 file next to the module and uses it if it exists.
+/// - Resolves tag names, either from the string table or from the tag path of the file.
+///
+/// # Examples
+///
+/// ```rust
+/// use infinite_rs::module::builder::ModuleFileBuilder;
+/// use infinite_rs::Result;
+///
+/// fn open_module() -> Result<()> {
+///     let module = ModuleFileBuilder::new()
+///         .use_hd1(false)
+///         .resolve_tag_names(false)
+///         .open("C:/XboxGames/Halo Infinite/Content/deploy/any/globals-rtx-new.module")?;
+///     Ok(())
+/// }
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+pub struct ModuleFileBuilder {
+    /// Path to the HD1 file, overriding the default location.
+    pub(super) hd1_path: Option<PathBuf>,
+    /// Whether to skip opening the HD1 file.
+    pub(super) disable_hd1: bool,
+    /// Already opened HD1 file, used instead of opening a new handle.
+    pub(super) shared_hd1: Option<SharedHd1>,
+    /// Whether to skip assigning tag names to file entries.
+    pub(super) skip_tag_names: bool,
+    /// Whether to read unknown newer module versions as the latest known version.
+    pub(super) allow_unknown_version: bool,
+    /// Whether to skip decompressing the resource sections of tags until they are requested.
+    pub(super) defer_resources: bool,
+}
+
+impl ModuleFileBuilder {
+    /// Creates a new builder with the default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the path of the HD1 file used by the module.
+    ///
+    /// # Arguments
+    ///
+    /// * `hd1_path` - A reference to a type that implements [`Path`] that holds the path to the HD1 file.
+    #[must_use]
+    pub fn hd1_path<T: AsRef<Path>>(mut self, hd1_path: T) -> Self {
+        self.hd1_path = Some(hd1_path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Uses an already opened HD1 file instead of opening a new handle to it.
+    ///
+    /// Modules reading from the same HD1 file can share a single handle, which keeps the number of open files low when
+    /// loading many modules. Reads from the handle are serialized using its [`Mutex`](`std::sync::Mutex`). The handle is
+    /// only used if the module requires an HD1 file and [`use_hd1`](`ModuleFileBuilder::use_hd1`) is not disabled. Set
+    /// [`hd1_path`](`ModuleFileBuilder::hd1_path`) as well for [`read_tag_owned`](`ModuleFile::read_tag_owned`) to
+    /// read files stored in it.
+    ///
+    /// # Arguments
+    ///
+    /// * `hd1_file` - Handle to the HD1 file, shared between modules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use infinite_rs::module::builder::ModuleFileBuilder;
+    /// use infinite_rs::Result;
+    ///
+    /// fn open_modules() -> Result<()> {
+    ///     let hd1 = "C:/XboxGames/Halo Infinite/Content/deploy/any/globals-rtx-new.module_hd1";
+    ///     let hd1_file = Arc::new(Mutex::new(BufReader::new(File::open(hd1)?)));
+    ///     let first = ModuleFileBuilder::new()
+    ///         .shared_hd1(Arc::clone(&hd1_file))
+    ///         .open("C:/XboxGames/Halo Infinite/Content/deploy/any/globals-rtx-new.module")?;
+    ///     let second = ModuleFileBuilder::new()
+    ///         .shared_hd1(hd1_file)
+    ///         .open("C:/XboxGames/Halo Infinite/Content/deploy/any/globals-rtx-new.module")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn shared_hd1(mut self, hd1_file: SharedHd1) -> Self {
+        self.shared_hd1 = Some(hd1_file);
+        self
+    }
+
+    /// Sets whether the HD1 file should be opened if the module requires it.
+    /// Defaults to `true`.
+    ///
+    /// If disabled, tags stored in the HD1 file will not be read.
+    #[must_use]
+    pub fn use_hd1(mut self, use_hd1: bool) -> Self {
+        self.disable_hd1 = !use_hd1;
+        self
+    }
+
+    /// Sets whether [`tag_name`](`crate::module::file::ModuleFileEntry::tag_name`) should be filled for each file entry.
+    /// Defaults to `true`.
+    ///
+    /// Disabling this skips reading the string table, which can speed up loading when names are not needed.
+    #[must_use]
+    pub fn resolve_tag_names(mut self, resolve_tag_names: bool) -> Self {
+        self.skip_tag_names = !resolve_tag_names;
+        self
+    }
+
+    /// Sets whether modules with an unknown version newer than
+    /// [`ModuleVersion::Season3`](`crate::module::header::ModuleVersion::Season3`) should be read.
+    /// Defaults to `false`.
+    ///
+    /// If enabled, such modules are read as [`ModuleVersion::Season3`](`crate::module::header::ModuleVersion::Season3`),
+    /// and the version stored in the file is kept in
+    /// [`raw_version`](`crate::module::header::ModuleHeader::raw_version`). This allows tools to keep working when a new
+    /// build of the game is released, though reading may fail if the layout of the module has changed.
+    /// A warning is logged if the `tracing` feature is enabled.
+    #[must_use]
+    pub fn allow_unknown_version(mut self, allow_unknown_version: bool) -> Self {
+        self.allow_unknown_version = allow_unknown_version;
+        self
+    }
+
+    /// Sets whether [`read_tag`](`ModuleFile::read_tag`) should only decompress the header and tag data of files.
+    /// Defaults to `false`.
+    ///
+    /// Resource sections (such as bitmap or havok data) can be much larger than the metadata of a tag. If enabled, the
+    /// blocks that only contain resource sections are skipped and left zeroed in the
+    /// [`data_stream`](`crate::module::file::ModuleFileEntry::data_stream`), until they are loaded with
+    /// [`load_resources`](`ModuleFile::load_resources`). Files stored as a single block are always read in full.
+    #[must_use]
+    pub fn defer_resources(mut self, defer_resources: bool) -> Self {
+        self.defer_resources = defer_resources;
+        self
+    }
+
+    /// Opens and reads the module file with the configuration of the builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - A reference to a type that implements [`Path`] that holds the path to the module file.
+    ///
+    /// # Errors
+    /// - If the module fails to be read [`ModuleFile::read`]
+    pub fn open<T: AsRef<Path>>(self, file_path: T) -> Result<ModuleFile> {
+        let mut module = ModuleFile::default();
+        module.config = self;
+        module.read(file_path)?;
+        Ok(module)
+    }
+}
@@ -0,0 +1,90 @@
+//! Comparison of the tags contained in two modules, built from their parsed headers.
+
+use std::collections::BTreeMap;
+
+use super::loader::ModuleFile;
+use crate::common::ids::TagId;
+
+#[derive(Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Differences between the tags of two modules, returned by [`ModuleFile::diff`].
+///
+/// Tags are matched by their [`tag_id`](`crate::module::file::ModuleFileEntry::tag_id`), and compared using their
+/// [`asset_hash`](`crate::module::file::ModuleFileEntry::asset_hash`). Resources (files without a tag ID) are not compared.
+/// All lists are sorted by tag ID, and the diff can be serialized with the `serde` feature.
+pub struct ModuleDiff {
+    /// Tags only present in the new module.
+    pub added: Vec<TagId>,
+    /// Tags only present in the old module.
+    pub removed: Vec<TagId>,
+    /// Tags present in both modules whose asset hash differs.
+    pub changed: Vec<TagId>,
+}
+
+impl ModuleDiff {
+    /// Compares the tags of two modules.
+    pub(super) fn new(old: &ModuleFile, new: &ModuleFile) -> Self {
+        let old_hashes = asset_hashes(old);
+        let new_hashes = asset_hashes(new);
+
+        let mut diff = Self::default();
+        for (tag_id, hash) in &old_hashes {
+            match new_hashes.get(tag_id) {
+                None => diff.removed.push(*tag_id),
+                Some(new_hash) if new_hash != hash => diff.changed.push(*tag_id),
+                Some(_) => {}
+            }
+        }
+        diff.added = new_hashes
+            .keys()
+            .filter(|tag_id| !old_hashes.contains_key(tag_id))
+            .copied()
+            .collect();
+        diff
+    }
+
+    /// Checks whether both modules contain the same tags with the same asset hashes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Maps the tag ID of each tag in the module to its asset hash. If a tag ID appears more than once, the first file is used.
+fn asset_hashes(module: &ModuleFile) -> BTreeMap<TagId, i128> {
+    let mut hashes = BTreeMap::new();
+    for file in module.files.iter().filter(|file| file.tag_id != -1) {
+        hashes.entry(TagId(file.tag_id)).or_insert(file.asset_hash);
+    }
+    hashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::file::tests::entry;
+
+    fn module(tags: &[(i32, i128)]) -> ModuleFile {
+        let mut module = ModuleFile::default();
+        for &(tag_id, asset_hash) in tags {
+            let mut entry = entry("", tag_id);
+            entry.asset_hash = asset_hash;
+            module.files.push(entry);
+        }
+        module
+    }
+
+    #[test]
+    /// Verifies that added, removed and changed tags are reported in order of their IDs, ignoring resources.
+    fn test_diff() {
+        let old = module(&[(3, 30), (1, 10), (2, 20), (-1, 0)]);
+        let new = module(&[(2, 21), (1, 10), (5, 50), (4, 40), (-1, 1)]);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, [TagId(4), TagId(5)]);
+        assert_eq!(diff.removed, [TagId(3)]);
+        assert_eq!(diff.changed, [TagId(2)]);
+        assert!(!diff.is_empty());
+        assert!(old.diff(&old).is_empty());
+    }
+}
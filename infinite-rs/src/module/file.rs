@@ -2,21 +2,31 @@
 
 use bitflags::bitflags;
 use byteorder::{ReadBytesExt, LE};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::{
-    fs::File,
-    io::{BufReader, Cursor, Read, Seek, SeekFrom},
-};
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom, Write};
+use std::mem;
+use std::ops::Range;
 
 use super::header::ModuleVersion;
 use super::{block::ModuleBlockEntry, kraken::decompress};
-use crate::common::errors::{ModuleError, TagError};
-use crate::tag::datablock::TagDataBlock;
-use crate::tag::structure::{TagStruct, TagStructType};
+use crate::common::errors::{DecompressionError, ModuleError, TagError};
+use crate::common::logging;
+use crate::tag::datablock::{TagDataBlock, TagSectionType};
+use crate::tag::group::{fourcc_matches, FourCc, TagGroup};
+use crate::tag::structure::TagStruct;
+use crate::tag::types::common_types::{AnyTag, FieldData, FieldReference};
 use crate::{common::extensions::BufReaderExt, tag::loader::TagFile};
 use crate::{Error, Result};
 
+/// Mask of the offset stored in the first 6 bytes of the raw data offset of a file entry.
+const DATA_OFFSET_MASK: u64 = 0x0000_FFFF_FFFF_FFFF;
+
+/// Size of the buffer used to stream decompressed data in [`ModuleFileEntry::copy_to`].
+const COPY_BUFFER_SIZE: usize = 0x10000;
+
 /// Trait for defining tag structures.
 ///
 /// This trait is meant to be used with its derive macro, available in the `derive` feature.
@@ -28,6 +38,13 @@ use crate::{Error, Result};
 /// For each of its fields, the following attributes are required:
 /// - `#[data(offset())]` - The offset in bytes from the start of the tag structure.
 ///
+/// Fields can optionally be marked with:
+/// - `#[data(min_version())]` - The first [`ModuleVersion`] (as its number, for instance `53` for
+///   [`Season3`](`ModuleVersion::Season3`)) containing the field. When reading tags from older modules, the field is
+///   skipped and keeps its default value. This applies to the main struct read by
+///   [`read_metadata`](`ModuleFileEntry::read_metadata`) and to the elements of its field blocks. The size of the
+///   structure should be the size in the newest version.
+///
 /// Any padding between fields should be accounted for in the offset.
 ///
 /// # Examples
@@ -45,23 +62,70 @@ use crate::{Error, Result};
 ///    any_tag: AnyTag,
 /// }
 ///
-/// fn load_tag() {
-///    let mut file_entry = ModuleFileEntry::default(); // In actual module, use reference to file.
-///    let mut material = MaterialTag::default();
-///    file_entry.read_metadata(&mut material).unwrap();
-///
-///    assert_eq!(material.size(), 0x30);
-///    assert_eq!(material.offsets().get("any_tag"), Some(&0x00));
+/// // Minimal tag containing a single main struct of 0x30 bytes. In an actual module, use a reference to the file.
+/// let mut tag = Vec::new();
+/// for value in [
+///     0x6873_6375, 27, 0, 0, 0, 0, // magic, version, root struct GUID and checksum
+///     0, 1, 1, 0, 0, // dependency, datablock, struct, data reference and tag reference counts
+///     0, 0, 0, // string table and zoneset sizes, unknown
+///     0x80, 0x30, 0, 0, 0, 0, // section sizes and alignments
+///     0x30, 1 << 16, 0, 0, // datablock of 0x30 bytes at the start of the tag data
+///     0, 0, 0, 0, 0, 0, -1, 0, // main struct, stored in the datablock
+/// ] {
+///     tag.extend_from_slice(&i32::to_le_bytes(value));
 /// }
+/// tag.resize(0xB0, 0);
+///
+/// let mut file_entry = ModuleFileEntry::from_bytes("mat ", tag).unwrap();
+/// let mut material = MaterialTag::default();
+/// file_entry.read_metadata(&mut material).unwrap();
+///
+/// assert_eq!(material.size(), 0x30);
+/// assert_eq!(material.offsets().get("any_tag"), Some(&0x00));
+/// assert_eq!(material.field_at_offset(0x00), Some("any_tag"));
 pub trait TagStructure {
     /// Returns the size of the tag structure in bytes.
     /// Determined by the [data(size())] attribute.
     fn size(&mut self) -> u64;
     /// Function that calls all [`read`](`crate::common::extensions::Enumerable::read`) functions for each field in the tag structure.
     fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()>;
+    /// Same as [`read`](`TagStructure::read`), but skips the fields whose `min_version` is newer than `version`.
+    /// Defaults to [`read`](`TagStructure::read`), for structures without versioned fields.
+    fn read_versioned<R: BufReaderExt>(
+        &mut self,
+        reader: &mut R,
+        version: &ModuleVersion,
+    ) -> Result<()> {
+        let _ = version;
+        self.read(reader)
+    }
     /// Returns a map of field names to their offsets in the tag structure.
     fn offsets(&self) -> HashMap<&'static str, u64>;
+    /// Returns the field names and their offsets in the tag structure, ordered by offset.
+    fn offsets_sorted(&self) -> Vec<(&'static str, u64)> {
+        let mut offsets: Vec<_> = self.offsets().into_iter().collect();
+        offsets.sort_by_key(|&(name, offset)| (offset, name));
+        offsets
+    }
+    /// Returns the name of the field starting at the given offset in the tag structure, if any.
+    fn field_at_offset(&self, offset: u64) -> Option<&'static str> {
+        self.offsets_sorted()
+            .into_iter()
+            .find(|&(_, field_offset)| field_offset == offset)
+            .map(|(name, _)| name)
+    }
+    /// Returns every [`FieldReference`] in the tag structure, including the ones inside elements of field blocks.
+    fn references(&self) -> Vec<&FieldReference> {
+        Vec::new()
+    }
+    /// Returns the number of elements loaded in every [`FieldBlock`](`crate::tag::types::common_types::FieldBlock`) of
+    /// the tag structure, including blocks inside elements of other blocks. This can be used to estimate the memory
+    /// used by a tag after its field blocks are loaded.
+    fn element_count(&self) -> usize {
+        0
+    }
     /// Function that loads all field blocks for the tag structure, if any.
+    /// Elements of the blocks are read with [`read_versioned`](`TagStructure::read_versioned`) using `version`.
     fn load_field_blocks<R: BufReaderExt>(
         &mut self,
         source_index: i32,
@@ -69,6 +133,7 @@ pub trait TagStructure {
         reader: &mut R,
         structs: &[TagStruct],
         blocks: &[TagDataBlock],
+        version: &ModuleVersion,
     ) -> Result<()>;
 }
 
@@ -85,6 +150,18 @@ bitflags! {
     }
 }
 
+/// Splits the raw data offset of a file entry into the offset (first 6 bytes) and its [`DataOffsetType`] flags (last 2 bytes).
+///
+/// The layout is the same in every [`ModuleVersion`], only the position of the field in the entry differs.
+fn split_data_offset(data_offset: u64) -> (u64, DataOffsetType) {
+    #[allow(clippy::cast_possible_truncation)]
+    let flags = (data_offset >> 48) as u16;
+    (
+        data_offset & DATA_OFFSET_MASK,
+        DataOffsetType::from_bits_retain(flags),
+    )
+}
+
 bitflags! {
     #[derive(Debug, Default, PartialEq, Eq)]
     /// Flags that determine how a tag should be read.
@@ -99,6 +176,105 @@ bitflags! {
     }
 }
 
+#[derive(Default, Debug, PartialEq, Eq)]
+/// Decompressed data of a file split into its sections, returned by [`split_sections`](`ModuleFileEntry::split_sections`).
+///
+/// Concatenating the sections in order results in the full decompressed data of the file.
+pub struct TagSections {
+    /// Tag header and tables, see [`TagFile`].
+    pub header: Vec<u8>,
+    /// Main tag data, containing the main struct and its blocks.
+    pub tag_data: Vec<u8>,
+    /// Data of resource child tags.
+    pub resource_data: Vec<u8>,
+    /// "External" resource data (for instance, bitmaps or havok data).
+    pub actual_resource: Vec<u8>,
+}
+
+#[derive(Debug)]
+/// Tag data read without modifying the module, returned by [`read_tag_owned`](`crate::ModuleFile::read_tag_owned`).
+pub struct LoadedTag {
+    /// Data stream containing a buffer of bytes to read/seek.
+    pub data_stream: BufReader<Cursor<Vec<u8>>>,
+    /// The actual tag file read from the contents (including header), only valid if file is not a resource.
+    pub tag_info: Option<TagFile>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Scalar metadata of a file entry as stored in the module, returned by [`entry_table`](`crate::ModuleFile::entry_table`).
+///
+/// Unlike [`ModuleFileEntry`], this contains every value of the file table (including the ones that are only used
+/// internally), and none of the loaded data. Flags are stored as their raw bits so that the record can be serialized
+/// with the `serde` feature.
+pub struct ModuleFileMeta {
+    /// Raw bits of [`flags`](`ModuleFileEntry::flags`).
+    pub flags: u8,
+    /// Number of blocks that make up the file.
+    pub block_count: u16,
+    /// Index of the first block in the module.
+    pub block_index: i32,
+    /// Index of the first resource in the module's resource list.
+    pub resource_index: i32,
+    /// Number of resources owned by the file.
+    pub resource_count: i32,
+    /// Tag group of the file.
+    pub tag_group: String,
+    /// Offset of the data of the file from the start of the file data in the module.
+    pub data_offset: u64,
+    /// Raw bits of [`data_offset_flags`](`ModuleFileEntry::data_offset_flags`).
+    pub data_offset_flags: u16,
+    /// Size in bytes of compressed buffer in module.
+    pub total_compressed_size: u32,
+    /// Size in bytes of buffer to decompress into.
+    pub total_uncompressed_size: u32,
+    /// Global tag ID of the file, -1 for resources.
+    pub tag_id: i32,
+    /// Sizes in bytes of the header, tag data, resource data and actual resource sections.
+    pub section_sizes: [u32; 4],
+    /// Alignments (as powers of 2) of the header, tag data, resource data and actual resource sections.
+    pub section_alignments: [u8; 4],
+    /// Offset of the name of the file in the string table.
+    pub name_offset: u32,
+    /// Index of the parent of the file, -1 if none.
+    pub parent_index: i32,
+    /// Hash of the file the entry was built from.
+    pub asset_hash: i128,
+}
+
+impl From<&ModuleFileEntry> for ModuleFileMeta {
+    fn from(entry: &ModuleFileEntry) -> Self {
+        Self {
+            flags: entry.flags.bits(),
+            block_count: entry.block_count,
+            block_index: entry.block_index,
+            resource_index: entry.resource_index,
+            resource_count: entry.resource_count,
+            tag_group: entry.tag_group.clone(),
+            data_offset: entry.data_offset,
+            data_offset_flags: entry.data_offset_flags.bits(),
+            total_compressed_size: entry.total_compressed_size,
+            total_uncompressed_size: entry.total_uncompressed_size,
+            tag_id: entry.tag_id,
+            section_sizes: [
+                entry.uncompressed_header_size,
+                entry.uncompressed_tag_data_size,
+                entry.uncompressed_resource_data_size,
+                entry.uncompressed_actual_resource_size,
+            ],
+            section_alignments: [
+                entry.header_alignment,
+                entry.tag_data_alignment,
+                entry.resource_data_alignment,
+                entry.actual_resource_data_alignment,
+            ],
+            name_offset: entry.name_offset,
+            parent_index: entry.parent_index,
+            asset_hash: entry.asset_hash,
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 /// Module file entry structure containing metadata relating to file and required buffer sizes and offsets for the decompressor, as well as global tag ID, resource references and class.
 pub struct ModuleFileEntry {
@@ -110,7 +286,8 @@ pub struct ModuleFileEntry {
     block_count: u16,
     /// Index of the first block in the module.
     block_index: i32,
-    /// Index of the first resource in the module's resource list.
+    /// Index of the first resource of the file in [`resource_indices`](`crate::ModuleFile::resource_indices`), see
+    /// [`resource_range`](`ModuleFileEntry::resource_range`).
     pub resource_index: i32,
     /// 4 byte-long string for tag group, stored as big endian. This determines how the rest of the tag is read.
     /// Example:
@@ -138,6 +315,7 @@ pub struct ModuleFileEntry {
     /// Size in bytes of "external" resource data in decompressed buffer. (for instance, havok data or bitmaps)
     pub uncompressed_actual_resource_size: u32,
     /// Power of 2 to align the header buffer to (ex w. 4 = align to a multiple of 16 bytes).
+    /// Alignments apply to sections inside the decompressed buffer, and do not affect where data is read from in the module.
     header_alignment: u8,
     /// Power of 2 to align the tag data buffer to.
     tag_data_alignment: u8,
@@ -154,7 +332,7 @@ pub struct ModuleFileEntry {
     /// This is not always the same thing as the file stored in the module.
     /// Only verified if the `HasBlocks` flag is not set.
     pub asset_hash: i128,
-    /// Number of resources owned by the file.
+    /// Number of resources owned by the file, see [`resource_range`](`ModuleFileEntry::resource_range`).
     pub resource_count: i32,
     /// Data stream containing a buffer of bytes to read/seek.
     pub data_stream: Option<BufReader<Cursor<Vec<u8>>>>,
@@ -162,6 +340,10 @@ pub struct ModuleFileEntry {
     pub tag_info: Option<TagFile>,
     /// Indicates if file is cached (has data stream) or not.
     pub is_loaded: bool,
+    /// Indicates if the blocks containing the resource sections were skipped when the file was loaded.
+    resources_deferred: bool,
+    /// Version of the module the file was loaded from, used to skip fields that do not exist in it.
+    module_version: ModuleVersion,
     /// Name of the tag as specified in the module string list.
     /// Set to tag id if module version does not support names.
     pub tag_name: String,
@@ -199,10 +381,8 @@ impl ModuleFileEntry {
         self.block_count = reader.read_u16::<LE>()?;
         self.resource_index = reader.read_i32::<LE>()?;
         self.block_index = reader.read_i32::<LE>()?;
-        self.tag_group = reader.read_fixed_string(4)?.chars().rev().collect(); // Reverse string
-        let data_offset = reader.read_u64::<LE>()?;
-        self.data_offset = data_offset & 0x0000_FFFF_FFFF_FFFF; // Mask first 6 bytes
-        self.data_offset_flags = DataOffsetType::from_bits_retain((data_offset >> 48) as u16); // Read last 2 bytes
+        self.tag_group = FourCc::read(reader)?.to_string();
+        (self.data_offset, self.data_offset_flags) = split_data_offset(reader.read_u64::<LE>()?);
         self.total_compressed_size = reader.read_u32::<LE>()?;
         self.total_uncompressed_size = reader.read_u32::<LE>()?;
         self.asset_hash = reader.read_i128::<LE>()?;
@@ -229,10 +409,8 @@ impl ModuleFileEntry {
         self.block_count = reader.read_u16::<LE>()?;
         self.block_index = reader.read_i32::<LE>()?;
         self.resource_index = reader.read_i32::<LE>()?;
-        self.tag_group = reader.read_fixed_string(4)?.chars().rev().collect(); // Reverse string
-        let data_offset = reader.read_u64::<LE>()?;
-        self.data_offset = data_offset & 0x0000_FFFF_FFFF_FFFF; // Mask first 6 bytes
-        self.data_offset_flags = DataOffsetType::from_bits_retain((data_offset >> 48) as u16); // Read last 2 bytes
+        self.tag_group = FourCc::read(reader)?.to_string();
+        (self.data_offset, self.data_offset_flags) = split_data_offset(reader.read_u64::<LE>()?);
         self.total_compressed_size = reader.read_u32::<LE>()?;
         self.total_uncompressed_size = reader.read_u32::<LE>()?;
         self.tag_id = reader.read_i32::<LE>()?;
@@ -255,24 +433,127 @@ impl ModuleFileEntry {
     ///
     /// # Arguments
     ///
-    /// * `reader` -  A mutable reference to a [`BufReader<File>`] from which to read the data.
+    /// * `reader` - A mutable reference to a reader implementing [`Read`] and [`Seek`] from which to read the data.
     /// * `data_offset` - Starting offset in bytes of the data in the file.
     /// * `blocks` - Metadata for data blocks.
     /// * `module_version` - Version of the module being read
+    /// * `defer_resources` - Whether to skip the blocks that only contain resource sections.
     ///
     /// # Errors
     /// - If the reader fails to read [`ReadError`](`crate::Error::ReadError`)
     /// - If any issues arise while reading non-raw tags: [`TagError`](`crate::common::errors::TagError`)
-    pub(super) fn read_tag(
+    pub(super) fn read_tag<R: Read + Seek>(
         &mut self,
-        reader: &mut BufReader<File>,
+        reader: &mut R,
         data_offset: u64,
         blocks: &[ModuleBlockEntry],
         module_version: &ModuleVersion,
+        defer_resources: bool,
     ) -> Result<()> {
         if self.is_loaded {
             return Ok(());
         }
+        let metadata_size = self.metadata_size();
+        // Single blocks cannot be partially decompressed, so they are always read in full.
+        let data = if defer_resources
+            && self.block_count != 0
+            && metadata_size < self.total_uncompressed_size
+        {
+            self.resources_deferred = true;
+            self.read_data_filtered(reader, data_offset, blocks, |block| {
+                block.decompressed_offset < metadata_size
+            })?
+        } else {
+            self.read_data(reader, data_offset, blocks)?
+        };
+        let mut data_stream = BufReader::new(Cursor::new(data));
+        self.tag_info = self.read_tag_info(&mut data_stream, module_version)?;
+        self.data_stream = Some(data_stream);
+        self.module_version = module_version.clone();
+
+        self.is_loaded = true;
+        Ok(())
+    }
+
+    /// Creates a loaded file entry from the decompressed data of a tag, without reading it from a module.
+    ///
+    /// The tag header is parsed into [`tag_info`](`ModuleFileEntry::tag_info`) and the section sizes of the entry are taken
+    /// from it, so functions such as [`read_metadata`](`ModuleFileEntry::read_metadata`) can be used directly. This is
+    /// mostly useful for tests and for tags that were already extracted. The tag is read as in [`ModuleVersion::Season3`]
+    /// modules, so names of dependencies are not read from the string table.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag_group` - 4 character code of the group of the tag, including trailing spaces.
+    /// * `bytes` - Decompressed data of the tag, starting with the tag header.
+    ///
+    /// # Errors
+    /// - If the size of the data does not fit in a [`u32`] [`TryFromIntError`](`crate::Error::TryFromIntError`)
+    /// - If any issues arise while reading the tag: [`TagError`](`crate::common::errors::TagError`)
+    pub fn from_bytes(tag_group: &str, bytes: Vec<u8>) -> Result<Self> {
+        let mut entry = Self {
+            tag_group: tag_group.to_string(),
+            total_uncompressed_size: u32::try_from(bytes.len())?,
+            ..Default::default()
+        };
+        let mut data_stream = BufReader::new(Cursor::new(bytes));
+        let tag_info = entry.read_tag_info(&mut data_stream, &ModuleVersion::Season3)?;
+        if let Some(tag_info) = &tag_info {
+            entry.uncompressed_header_size = tag_info.header.header_size;
+            entry.uncompressed_tag_data_size = tag_info.header.data_size;
+            entry.uncompressed_resource_data_size = tag_info.header.resource_size;
+            entry.uncompressed_actual_resource_size = tag_info.header.actual_resource_size;
+        }
+        entry.tag_info = tag_info;
+        entry.data_stream = Some(data_stream);
+        entry.is_loaded = true;
+        Ok(entry)
+    }
+
+    /// Reads and decompresses the data of the file into a new buffer.
+    ///
+    /// Unlike [`read_tag`](`ModuleFileEntry::read_tag`), this does not store anything in the file entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A mutable reference to a reader implementing [`Read`] and [`Seek`] from which to read the data.
+    /// * `data_offset` - Starting offset in bytes of the data in the file.
+    /// * `blocks` - Metadata for data blocks.
+    ///
+    /// # Errors
+    /// - If the reader fails to read [`ReadError`](`crate::Error::ReadError`)
+    /// - If the decompression operation fails [`Error::DecompressionError`]
+    pub(super) fn read_data<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        data_offset: u64,
+        blocks: &[ModuleBlockEntry],
+    ) -> Result<Vec<u8>> {
+        self.read_data_filtered(reader, data_offset, blocks, |_| true)
+    }
+
+    /// Reads and decompresses the blocks of the file accepted by `filter` into a new buffer.
+    ///
+    /// The buffer always spans the whole file, with the skipped blocks left zeroed. `filter` is ignored for files
+    /// stored as a single block.
+    ///
+    /// # Errors
+    /// - Same as [`read_data`](`ModuleFileEntry::read_data`).
+    fn read_data_filtered<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        data_offset: u64,
+        blocks: &[ModuleBlockEntry],
+        filter: impl Fn(&ModuleBlockEntry) -> bool,
+    ) -> Result<Vec<u8>> {
+        logging::debug!(
+            tag_group = %self.tag_group,
+            tag_id = self.tag_id,
+            compressed_size = self.total_compressed_size,
+            uncompressed_size = self.total_uncompressed_size,
+            block_count = self.block_count,
+            "reading file data"
+        );
         let file_offset = data_offset + self.data_offset;
         let mut data = vec![0u8; self.total_uncompressed_size as usize];
 
@@ -280,30 +561,123 @@ impl ModuleFileEntry {
         reader.rewind()?;
 
         if self.block_count != 0 {
-            self.read_multiple_blocks(reader, blocks, file_offset, &mut data)?;
+            self.read_multiple_blocks(reader, blocks, file_offset, &mut data, filter)?;
         } else {
             read_single_block(reader, self, file_offset, &mut data)?;
         }
+        Ok(data)
+    }
 
-        let data_stream = BufReader::new(Cursor::new(data));
-        self.data_stream = Some(data_stream);
-        if !self.flags.contains(FileEntryFlags::RAW_FILE) {
-            let mut tagfile = TagFile::default();
-            if let Some(ref mut stream) = self.data_stream {
-                if self.tag_group == "psod" {
-                    // HACK:: "psod" tags do not have string tables in any version.
-                    tagfile.read(stream, &ModuleVersion::Season3)?;
-                } else {
-                    tagfile.read(stream, module_version)?;
-                }
-            }
-            self.tag_info = Some(tagfile);
+    /// Decompresses the blocks skipped when the file was loaded with deferred resources into the
+    /// [`data_stream`](`ModuleFileEntry::data_stream`).
+    ///
+    /// The position of the data stream is kept. This does nothing if no blocks were deferred.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A mutable reference to a reader implementing [`Read`] and [`Seek`] from which to read the data.
+    /// * `data_offset` - Starting offset in bytes of the data in the file.
+    /// * `blocks` - Metadata for data blocks.
+    ///
+    /// # Errors
+    /// - If the tag data is not loaded [`TagError::NotLoaded`]
+    /// - Same as [`read_data`](`ModuleFileEntry::read_data`).
+    pub(super) fn load_deferred_resources<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        data_offset: u64,
+        blocks: &[ModuleBlockEntry],
+    ) -> Result<()> {
+        if !self.resources_deferred {
+            return Ok(());
         }
+        let (mut data, position) = {
+            let stream = self
+                .data_stream
+                .as_mut()
+                .ok_or(Error::TagError(TagError::NotLoaded))?;
+            let position = stream.stream_position()?;
+            (mem::take(stream.get_mut().get_mut()), position)
+        };
 
-        self.is_loaded = true;
+        let metadata_size = self.metadata_size();
+        reader.rewind()?;
+        let result = self.read_multiple_blocks(
+            reader,
+            blocks,
+            data_offset + self.data_offset,
+            &mut data,
+            |block| block.decompressed_offset >= metadata_size,
+        );
+
+        // The stream is rebuilt even on failure, as its buffer was taken out.
+        let mut stream = BufReader::new(Cursor::new(data));
+        stream.seek(SeekFrom::Start(position))?;
+        self.data_stream = Some(stream);
+        result?;
+        self.resources_deferred = false;
         Ok(())
     }
 
+    /// Gets whether the resource sections of the file were skipped when it was loaded.
+    ///
+    /// If so, the resource data and "actual" resource sections of the [`data_stream`](`ModuleFileEntry::data_stream`) are
+    /// zeroed until [`load_resources`](`crate::ModuleFile::load_resources`) is called. See
+    /// [`defer_resources`](`crate::module::builder::ModuleFileBuilder::defer_resources`).
+    #[must_use]
+    pub fn resources_deferred(&self) -> bool {
+        self.resources_deferred
+    }
+
+    /// Gets whether the data of the file was read from the module into [`data_stream`](`ModuleFileEntry::data_stream`).
+    ///
+    /// Equivalent to [`is_loaded`](`ModuleFileEntry#structfield.is_loaded`), without borrowing the entry mutably or
+    /// touching the data stream.
+    #[must_use]
+    pub fn is_loaded(&self) -> bool {
+        self.is_loaded
+    }
+
+    /// Gets whether the tag header was parsed into [`tag_info`](`ModuleFileEntry::tag_info`).
+    ///
+    /// This is only the case once the file is loaded, and never for raw files and resources, which have no tag header.
+    #[must_use]
+    pub fn has_metadata(&self) -> bool {
+        self.tag_info.is_some()
+    }
+
+    /// Size in bytes of the header and tag data sections, which are all that is needed to read the metadata of the tag.
+    fn metadata_size(&self) -> u32 {
+        self.uncompressed_header_size + self.uncompressed_tag_data_size
+    }
+
+    /// Reads the tag header and tables from decompressed data, if the file is not a raw file.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - A mutable reference to the decompressed data of the file.
+    /// * `module_version` - Version of the module being read
+    ///
+    /// # Errors
+    /// - If any issues arise while reading the tag: [`TagError`](`crate::common::errors::TagError`)
+    pub(super) fn read_tag_info(
+        &self,
+        stream: &mut BufReader<Cursor<Vec<u8>>>,
+        module_version: &ModuleVersion,
+    ) -> Result<Option<TagFile>> {
+        if self.flags.contains(FileEntryFlags::RAW_FILE) {
+            return Ok(None);
+        }
+        let mut tagfile = TagFile::default();
+        if self.tag_group == "psod" {
+            // HACK:: "psod" tags do not have string tables in any version.
+            tagfile.read(stream, &ModuleVersion::Season3)?;
+        } else {
+            tagfile.read(stream, module_version)?;
+        }
+        Ok(Some(tagfile))
+    }
+
     /// Reads multiple blocks of data from the file.
     ///
     /// This function reads multiple blocks of data, which can be either compressed or uncompressed,
@@ -311,24 +685,33 @@ impl ModuleFileEntry {
     ///
     /// # Arguments
     ///
-    /// * `reader` - A mutable reference to a [`BufReader<File>`] from which to read the data.
+    /// * `reader` - A mutable reference to a reader implementing [`Read`] and [`Seek`] from which to read the data.
     /// * `blocks` - A slice of [`ModuleBlockEntry`] containing metadata about each block.
-    /// * `file_offset` - The offset in the file where the data blocks start.
+    /// * `file_offset` - The offset in the file where the data of the file entry starts. Each block is read from
+    ///   its [`compressed_offset`](`ModuleBlockEntry::compressed_offset`) after this offset, so blocks do not need to be
+    ///   contiguous.
     /// * `data` - A mutable slice where the (decompressed) data will be stored.
+    /// * `filter` - Function deciding whether each block is read. Skipped blocks are left untouched in `data`.
     ///
     /// # Errors
     /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
     /// - If the block index is negative [`ModuleError::NegativeBlockIndex`]
+    /// - If the blocks of the file are out of bounds [`ModuleError::InvalidIndex`]
     ///
     /// # Safety
     /// - This function has an unsafe component because it can call the [`read_compressed_block`] function, which uses [`decompress`] which is unsafe.
+    ///
+    /// With the `rayon` feature, compressed blocks are decompressed in parallel after all blocks have been read, see
+    /// [`decompress_blocks_parallel`].
     #[allow(clippy::cast_sign_loss)]
-    fn read_multiple_blocks(
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn read_multiple_blocks<R: Read + Seek>(
         &self,
-        reader: &mut BufReader<File>,
+        reader: &mut R,
         blocks: &[ModuleBlockEntry],
         file_offset: u64,
         data: &mut [u8],
+        filter: impl Fn(&ModuleBlockEntry) -> bool,
     ) -> Result<()> {
         if self.block_index < 0 {
             return Err(Error::ModuleError(ModuleError::NegativeBlockIndex(
@@ -336,23 +719,116 @@ impl ModuleFileEntry {
             )));
         }
         let first_block_index = self.block_index as usize;
-        reader.seek(SeekFrom::Start(file_offset))?;
-
-        let initial_block_offset = reader.stream_position()?;
-        for block in &blocks[first_block_index..(first_block_index + self.block_count as usize)] {
-            // even though blocks are sequential, we still should seek to the correct position.
+        let file_blocks = blocks
+            .get(first_block_index..(first_block_index + self.block_count as usize))
+            .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
+        #[cfg(feature = "rayon")]
+        let mut compressed_blocks = Vec::new();
+        for block in file_blocks.iter().filter(|block| filter(block)) {
+            // blocks are usually stored back to back, but they may have gaps between them or be skipped by the filter.
             reader.seek(SeekFrom::Start(
-                initial_block_offset + u64::from(block.compressed_offset),
+                file_offset + u64::from(block.compressed_offset),
             ))?;
+            logging::debug!(
+                compressed_size = block.compressed_size,
+                decompressed_size = block.decompressed_size,
+                is_compressed = block.is_compressed,
+                "reading block"
+            );
             if block.is_compressed {
-                unsafe { read_compressed_block(reader, block, data)? };
+                #[cfg(feature = "rayon")]
+                {
+                    // Compressed blocks are only read here, and decompressed together once all blocks are read.
+                    let mut compressed_data = vec![0u8; block.compressed_size as usize];
+                    reader.read_exact(&mut compressed_data)?;
+                    compressed_blocks.push((block, compressed_data));
+                }
+                #[cfg(not(feature = "rayon"))]
+                unsafe {
+                    read_compressed_block(reader, block, data)?;
+                };
             } else {
                 read_uncompressed_block(reader, block, data)?;
             }
         }
+        #[cfg(feature = "rayon")]
+        decompress_blocks_parallel(compressed_blocks, data)?;
         Ok(())
     }
 
+    /// Gets the group of the tag, parsed from [`tag_group`](`ModuleFileEntry::tag_group`).
+    #[must_use]
+    pub fn group(&self) -> TagGroup {
+        TagGroup::from_fourcc(&self.tag_group)
+    }
+
+    /// Gets the range of the resources owned by the file in [`resource_indices`](`crate::ModuleFile::resource_indices`).
+    ///
+    /// The range indexes into `resource_indices`, whose values are indices of the resource files in
+    /// [`files`](`crate::ModuleFile::files`). It is built from [`resource_index`](`ModuleFileEntry::resource_index`) and
+    /// [`resource_count`](`ModuleFileEntry::resource_count`), and is empty if the file has no resources or either value
+    /// is negative.
+    #[must_use]
+    pub fn resource_range(&self) -> Range<usize> {
+        match (
+            usize::try_from(self.resource_index),
+            usize::try_from(self.resource_count),
+        ) {
+            (Ok(start), Ok(count)) => start..start + count,
+            _ => 0..0,
+        }
+    }
+
+    /// Checks whether the tag belongs to a group, ignoring trailing spaces.
+    ///
+    /// Unlike comparing [`tag_group`](`ModuleFileEntry::tag_group`) directly, both `"mat"` and `"mat "` match material tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The 4 character code of the group, with or without trailing spaces.
+    #[must_use]
+    pub fn is_group(&self, group: &str) -> bool {
+        fourcc_matches(&self.tag_group, group)
+    }
+
+    /// Gets the length in bytes of the decompressed data in [`data_stream`](`ModuleFileEntry::data_stream`).
+    ///
+    /// Unlike reading the stream, this does not change its position. Returns [`None`] if the file is not loaded.
+    #[must_use]
+    pub fn decompressed_len(&self) -> Option<u64> {
+        self.data_stream
+            .as_ref()
+            .map(|stream| stream.get_ref().get_ref().len() as u64)
+    }
+
+    /// Gets the alignment in bytes of the "actual" resource section (such as bitmap pixels or havok data).
+    ///
+    /// This is informational only. Sections are stored back to back in the decompressed data, as described by the
+    /// `uncompressed_*_size` fields, and any padding the alignment requires is already included in the size of the previous
+    /// section. Payload offsets must therefore not be aligned again, which is why functions such as
+    /// [`split_sections`](`ModuleFileEntry::split_sections`) and [`read_field_data`](`ModuleFileEntry::read_field_data`)
+    /// use the stored offsets as-is.
+    ///
+    /// Returns [`None`] if the stored power of two does not fit in a [`u64`].
+    #[must_use]
+    pub fn actual_resource_alignment(&self) -> Option<u64> {
+        1u64.checked_shl(u32::from(self.actual_resource_data_alignment))
+    }
+
+    /// Gets the index of the first block making up the file in the module's [`blocks`](`crate::module::loader::ModuleFile::blocks`).
+    #[must_use]
+    pub fn block_index(&self) -> i32 {
+        self.block_index
+    }
+
+    /// Gets the number of blocks making up the file.
+    ///
+    /// If this is zero, the file is stored as a single (possibly compressed) block of [`total_compressed_size`](`ModuleFileEntry::total_compressed_size`) bytes.
+    #[must_use]
+    pub fn block_count(&self) -> u16 {
+        self.block_count
+    }
+
     /// Reads a specified structure implementing [`TagStructure`] from the tag data.
     ///
     /// This function exhausts the inner [`data_stream`](`ModuleFileEntry::data_stream`) buffer to read the contents of the specified
@@ -360,20 +836,214 @@ impl ModuleFileEntry {
     /// data block and creates a reader for it. The initial contents of the struct are read, and
     /// field block definitions are loaded recursively.
     ///
+    /// Datablock offsets are used as-is: they are relative to the start of the tag data, and already account for the
+    /// alignment of blocks inside it. The section alignments of the entry only apply to how the game lays out sections in
+    /// memory, and are not applied here.
+    ///
+    /// Files without a tag header (such as the entries of resource-only modules, where [`tag_id`](`ModuleFileEntry::tag_id`) is `-1`)
+    /// cannot be read with this function. Use [`read_resource_blob`](`crate::module::loader::ModuleFile::read_resource_blob`) instead.
     ///
     /// # Arguments
     ///
     /// * `struct_type` - A mutable reference to the struct implementing [`TagStructure`] to read the data into.
     ///
     /// # Errors
+    /// - If the header size of the entry is larger than its total size [`TagError::InvalidHeaderSize`]
     /// - If the tag data is not loaded [`TagError::NotLoaded`]
     /// - If the tag info is not present [`TagError::NoTagInfo`]
     /// - If the main struct definition is not found [`TagError::MainStructNotFound`]
+    /// - If the main struct points to a datablock that does not exist [`TagError::BlockIndexOutOfRange`]
     /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(tag_group = %self.tag_group, tag_id = self.tag_id))
+    )]
     pub fn read_metadata<T: Default + TagStructure>(&mut self, struct_type: &mut T) -> Result<T> {
-        let mut full_tag = Vec::with_capacity(
-            self.total_uncompressed_size as usize - self.uncompressed_header_size as usize,
-        );
+        self.read_main_struct(struct_type)?;
+        Ok(T::default())
+    }
+
+    /// Reads a specified structure implementing [`TagStructure`] from the tag data, and returns the raw bytes of the main struct.
+    ///
+    /// This is the same as [`read_metadata`](`ModuleFileEntry::read_metadata`), but also returns the bytes the struct was read from.
+    /// Comparing the parsed fields against the raw bytes helps finding data that the structure does not model yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `struct_type` - A mutable reference to the struct implementing [`TagStructure`] to read the data into.
+    ///
+    /// # Returns
+    ///
+    /// Returns the bytes of the main struct, which span [`entry_size`](`TagDataBlock::entry_size`) bytes of its datablock.
+    ///
+    /// # Errors
+    /// - If the tag data is not loaded [`TagError::NotLoaded`]
+    /// - If the tag info is not present [`TagError::NoTagInfo`]
+    /// - If the main struct definition is not found [`TagError::MainStructNotFound`]
+    /// - If the main struct points to a datablock that does not exist [`TagError::BlockIndexOutOfRange`]
+    /// - If the main struct datablock is outside of the tag data [`TagError::DataOutOfBounds`]
+    /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    pub fn read_metadata_with_bytes<T: TagStructure>(
+        &mut self,
+        struct_type: &mut T,
+    ) -> Result<Vec<u8>> {
+        let (mut full_tag, main_offset, main_size) = self.read_main_struct(struct_type)?;
+        let main_end = main_offset
+            .checked_add(main_size)
+            .filter(|&end| end <= full_tag.len())
+            .ok_or(Error::TagError(TagError::DataOutOfBounds))?;
+        full_tag.truncate(main_end);
+        full_tag.drain(..main_offset);
+        Ok(full_tag)
+    }
+
+    /// Reads only the [`AnyTag`] at the start of the main struct of the tag.
+    ///
+    /// This reads [`AnyTag::SIZE`] bytes instead of the whole tag data, and does not consume the
+    /// [`data_stream`](`ModuleFileEntry::data_stream`), so it can be used before or after [`read_metadata`](`ModuleFileEntry::read_metadata`).
+    /// It is useful for building maps of tag IDs, or for checking that a tag was read correctly.
+    ///
+    /// # Errors
+    /// - If the tag data is not loaded [`TagError::NotLoaded`]
+    /// - If the tag info is not present (for instance for resources or raw files) [`TagError::NoTagInfo`]
+    /// - If the main struct definition is not found [`TagError::MainStructNotFound`]
+    /// - If the main struct points to a datablock that does not exist [`TagError::BlockIndexOutOfRange`]
+    /// - If the main struct datablock is outside of the tag data [`TagError::DataOutOfBounds`]
+    pub fn read_any_tag(&self) -> Result<AnyTag> {
+        let data = self
+            .data_stream
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NotLoaded))?
+            .get_ref()
+            .get_ref();
+        let tag_info = self
+            .tag_info
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NoTagInfo))?;
+        let main_struct = tag_info
+            .main_struct()
+            .ok_or(Error::TagError(TagError::MainStructNotFound))?;
+
+        let main_block = tag_info.datablock(main_struct.target_index)?;
+        let start = usize::try_from(tag_info.absolute_offset(main_block))?;
+        let bytes = start
+            .checked_add(usize::try_from(AnyTag::SIZE)?)
+            .and_then(|end| data.get(start..end))
+            .ok_or(Error::TagError(TagError::DataOutOfBounds))?;
+
+        let mut any_tag = AnyTag::default();
+        any_tag.read(&mut Cursor::new(bytes))?;
+        Ok(any_tag)
+    }
+
+    /// Checks that the tag ID stored in the [`AnyTag`] of the tag matches [`tag_id`](`ModuleFileEntry::tag_id`).
+    ///
+    /// Both should always agree. A mismatch means that the tag was not read from the right offset, or that the module is corrupt.
+    ///
+    /// # Errors
+    /// - Same as [`read_any_tag`](`ModuleFileEntry::read_any_tag`).
+    pub fn verify_any_tag(&self) -> Result<bool> {
+        Ok(self.read_any_tag()?.internal_struct.tag_id == self.tag_id)
+    }
+
+    /// Reads the elements of a block from the tag data, finding the block by the GUID of its struct definition.
+    ///
+    /// This allows reading blocks whose GUID is only known at runtime, without a structure for the main struct or the
+    /// other blocks containing it. The number of elements is the size of the datablock of the struct divided by the size
+    /// of `T`, and the field blocks of each element are loaded recursively. If several struct definitions share the GUID
+    /// (for instance blocks of the same type in different elements), the first one pointing to a datablock is read.
+    /// Unlike [`read_metadata`](`ModuleFileEntry::read_metadata`), the [`data_stream`](`ModuleFileEntry::data_stream`)
+    /// is not consumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `struct_guid` - The [`guid`](`TagStruct::guid`) of the struct definition of the block.
+    ///
+    /// # Returns
+    ///
+    /// Returns the elements of the block, which is empty if no struct definition with the GUID points to a datablock.
+    ///
+    /// # Errors
+    /// - If the tag data is not loaded [`TagError::NotLoaded`]
+    /// - If the tag info is not present [`TagError::NoTagInfo`]
+    /// - If no struct definition has the GUID [`TagError::StructNotFound`]
+    /// - If the struct points to a datablock that does not exist [`TagError::BlockIndexOutOfRange`]
+    /// - If the tag data section is outside of the loaded data [`TagError::DataOutOfBounds`]
+    /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    pub fn read_block_as<T: Default + TagStructure>(&self, struct_guid: u128) -> Result<Vec<T>> {
+        let data = self
+            .data_stream
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NotLoaded))?
+            .get_ref()
+            .get_ref();
+        let tag_info = self
+            .tag_info
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NoTagInfo))?;
+
+        let mut candidates = tag_info
+            .struct_definitions
+            .iter()
+            .filter(|s| s.guid == struct_guid)
+            .peekable();
+        if candidates.peek().is_none() {
+            return Err(Error::TagError(TagError::StructNotFound(struct_guid)));
+        }
+        let Some(block_struct) = candidates.find(|s| s.target_index != -1) else {
+            return Ok(Vec::new());
+        };
+        let block = tag_info.datablock(block_struct.target_index)?;
+
+        // Datablock offsets are relative to the start of the tag data, as in `read_main_struct`.
+        let tag_data_offset = usize::try_from(tag_info.section_offset(&TagSectionType::TagData))?;
+        let tag_data = data
+            .get(tag_data_offset..)
+            .ok_or(Error::TagError(TagError::DataOutOfBounds))?;
+        let mut reader = BufReader::new(Cursor::new(tag_data));
+        reader.seek(SeekFrom::Start(block.offset))?;
+
+        let size = T::default().size();
+        let count = u64::from(block.entry_size).checked_div(size).unwrap_or(0);
+        let mut elements = Vec::new();
+        for _ in 0..count {
+            let mut element = T::default();
+            element.read_versioned(&mut reader, &self.module_version)?;
+            elements.push(element);
+        }
+        for (index, element) in elements.iter_mut().enumerate() {
+            element.load_field_blocks(
+                block_struct.target_index,
+                size * index as u64,
+                &mut reader,
+                &tag_info.struct_definitions,
+                &tag_info.datablock_definitions,
+                &self.module_version,
+            )?;
+        }
+        Ok(elements)
+    }
+
+    /// Reads the main struct of the tag into `struct_type`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the tag data the struct was read from, along with the offset and size of the main struct in it.
+    ///
+    /// # Errors
+    /// - Same as [`read_metadata`](`ModuleFileEntry::read_metadata`).
+    fn read_main_struct<T: TagStructure>(
+        &mut self,
+        struct_type: &mut T,
+    ) -> Result<(Vec<u8>, usize, usize)> {
+        let tag_size = self
+            .total_uncompressed_size
+            .checked_sub(self.uncompressed_header_size)
+            .ok_or(Error::TagError(TagError::InvalidHeaderSize(
+                self.uncompressed_header_size,
+                self.total_uncompressed_size,
+            )))?;
+        let mut full_tag = Vec::with_capacity(tag_size as usize);
         self.data_stream
             .as_mut()
             .ok_or(Error::TagError(TagError::NotLoaded))?
@@ -385,27 +1055,175 @@ impl ModuleFileEntry {
             .ok_or(Error::TagError(TagError::NoTagInfo))?;
 
         let main_struct = tag_info
-            .struct_definitions
-            .iter()
-            .find(|s| s.struct_type == TagStructType::MainStruct)
+            .main_struct()
             .ok_or(Error::TagError(TagError::MainStructNotFound))?;
 
-        #[allow(clippy::cast_sign_loss)]
-        let main_block: &TagDataBlock =
-            &tag_info.datablock_definitions[main_struct.target_index as usize];
-        let full_tag_buffer = &full_tag[usize::try_from(main_block.offset)?..];
-        let mut full_tag_reader = BufReader::new(Cursor::new(full_tag_buffer));
+        let main_block = tag_info.datablock(main_struct.target_index)?;
+        let main_offset = usize::try_from(main_block.offset)?;
+        if main_offset > full_tag.len() {
+            return Err(Error::TagError(TagError::DataOutOfBounds));
+        }
+        // Datablock offsets are relative to the start of the tag data, so the reader has to cover all of it for
+        // nested blocks to be found, not just the data starting at the main struct.
+        let mut full_tag_reader = BufReader::new(Cursor::new(&full_tag[..]));
+        full_tag_reader.seek(SeekFrom::Start(main_block.offset))?;
 
-        struct_type.read(&mut full_tag_reader)?;
+        struct_type.read_versioned(&mut full_tag_reader, &self.module_version)?;
         struct_type.load_field_blocks(
             main_struct.target_index,
             0,
             &mut full_tag_reader,
             &tag_info.struct_definitions[..],
             &tag_info.datablock_definitions[..],
+            &self.module_version,
         )?;
 
-        Ok(T::default())
+        let main_size = main_block.entry_size as usize;
+        Ok((full_tag, main_offset, main_size))
+    }
+
+    /// Splits the decompressed data of the file into its header, tag data, resource data and "actual" resource sections.
+    ///
+    /// The sizes of the sections are taken from the `uncompressed_*_size` fields of the entry. This allows tools to edit a
+    /// section and assemble the file again from the unchanged sections.
+    ///
+    /// # Errors
+    /// - If the tag data is not loaded [`TagError::NotLoaded`]
+    /// - If the section sizes do not match the size of the data [`TagError::DataOutOfBounds`]
+    pub fn split_sections(&self) -> Result<TagSections> {
+        let data = self
+            .data_stream
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NotLoaded))?
+            .get_ref()
+            .get_ref();
+        self.split_data(data)
+    }
+
+    /// Writes the decompressed data of the file to a writer, without copying it into an intermediate buffer.
+    ///
+    /// The [`data_stream`](`ModuleFileEntry::data_stream`) is rewound and streamed through a fixed-size buffer, so extracting
+    /// large resources does not require a second allocation of their full size. The stream is left at its end.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to copy the data to.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of bytes written.
+    ///
+    /// # Errors
+    /// - If the tag data is not loaded [`TagError::NotLoaded`]
+    /// - If reading from the stream or writing to the writer fails [`ReadError`](`crate::Error::ReadError`)
+    pub fn copy_to<W: Write>(&mut self, writer: &mut W) -> Result<u64> {
+        let stream = self
+            .data_stream
+            .as_mut()
+            .ok_or(Error::TagError(TagError::NotLoaded))?;
+        stream.rewind()?;
+
+        let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+        let mut written = 0;
+        loop {
+            let read = stream.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            writer.write_all(&buffer[..read])?;
+            written += read as u64;
+        }
+        Ok(written)
+    }
+
+    /// Splits decompressed data of the file into its sections, as done by [`split_sections`](`ModuleFileEntry::split_sections`).
+    ///
+    /// # Errors
+    /// - If the section sizes do not match the size of the data [`TagError::DataOutOfBounds`]
+    pub(super) fn split_data(&self, data: &[u8]) -> Result<TagSections> {
+        let mut rest = data;
+        let mut take = |size: u32| -> Result<Vec<u8>> {
+            let size = size as usize;
+            if size > rest.len() {
+                return Err(Error::TagError(TagError::DataOutOfBounds));
+            }
+            let (section, remaining) = rest.split_at(size);
+            rest = remaining;
+            Ok(section.to_vec())
+        };
+        let sections = TagSections {
+            header: take(self.uncompressed_header_size)?,
+            tag_data: take(self.uncompressed_tag_data_size)?,
+            resource_data: take(self.uncompressed_resource_data_size)?,
+            actual_resource: take(self.uncompressed_actual_resource_size)?,
+        };
+        if !rest.is_empty() {
+            return Err(Error::TagError(TagError::DataOutOfBounds));
+        }
+        Ok(sections)
+    }
+
+    /// Gets the bytes referenced by a [`FieldData`] read from this tag.
+    ///
+    /// The data of a [`FieldData`] field is not stored inline, but in a separate datablock pointed to by a
+    /// [`TagDataReference`](`crate::tag::data_reference::TagDataReference`). Depending on the
+    /// [`section_type`](`crate::tag::datablock::TagDataBlock::section_type`) of that datablock, the bytes are located in
+    /// the tag data, resource data or "actual" resource section of the file, which is accounted for here.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - A [`FieldData`] that was read from this tag using [`read_metadata`](`ModuleFileEntry::read_metadata`).
+    ///
+    /// # Returns
+    ///
+    /// Returns the referenced bytes, or [`None`] if the field does not have a data reference or the reference is null.
+    ///
+    /// # Errors
+    /// - If the tag data is not loaded [`TagError::NotLoaded`]
+    /// - If the tag info is not present [`TagError::NoTagInfo`]
+    /// - If the referenced data is in a resource section that was not loaded [`TagError::ResourcesDeferred`]
+    /// - If the referenced data is outside of the tag [`TagError::DataOutOfBounds`]
+    pub fn read_field_data(&self, field: &FieldData) -> Result<Option<&[u8]>> {
+        let tag_info = self
+            .tag_info
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NoTagInfo))?;
+        let data = self
+            .data_stream
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NotLoaded))?
+            .get_ref()
+            .get_ref();
+
+        let Some(reference) = tag_info.data_reference_at(field.field_offset) else {
+            return Ok(None);
+        };
+        let Ok(target_index) = usize::try_from(reference.target_index) else {
+            return Ok(None);
+        };
+        let block = tag_info
+            .datablock_definitions
+            .get(target_index)
+            .ok_or(Error::TagError(TagError::DataOutOfBounds))?;
+        if self.resources_deferred
+            && matches!(
+                block.section_type,
+                TagSectionType::ResourceData | TagSectionType::ActualResource
+            )
+        {
+            return Err(Error::TagError(TagError::ResourcesDeferred));
+        }
+        let start = tag_info
+            .section_offset(&block.section_type)
+            .checked_add(block.offset)
+            .ok_or(Error::TagError(TagError::DataOutOfBounds))?;
+        let start = usize::try_from(start)?;
+        let end = start
+            .checked_add(field.size as usize)
+            .ok_or(Error::TagError(TagError::DataOutOfBounds))?;
+        data.get(start..end)
+            .map(Some)
+            .ok_or(Error::TagError(TagError::DataOutOfBounds))
     }
 }
 
@@ -416,14 +1234,14 @@ impl ModuleFileEntry {
 ///
 /// # Arguments
 ///
-/// * `reader` - A mutable reference to a [`BufReader<File>`] from which to read the data.
+/// * `reader` - A mutable reference to a reader implementing [`Read`] and [`Seek`] from which to read the data.
 /// * `block` - A reference to the [`ModuleBlockEntry`] containing metadata about the block.
 /// * `data` - A mutable slice where the uncompressed data will be stored.
 ///
 /// # Errors
 /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
-fn read_uncompressed_block(
-    reader: &mut BufReader<File>,
+fn read_uncompressed_block<R: Read + Seek>(
+    reader: &mut R,
     block: &ModuleBlockEntry,
     data: &mut [u8],
 ) -> Result<()> {
@@ -441,18 +1259,20 @@ fn read_uncompressed_block(
 ///
 /// # Arguments
 ///
-/// * `reader` - A mutable reference to a [`BufReader<File>`] from which to read the data.
+/// * `reader` - A mutable reference to a reader implementing [`Read`] and [`Seek`] from which to read the data.
 /// * `block` - A reference to the [`ModuleBlockEntry`] containing metadata about the block.
 /// * `data` - A mutable slice where the decompressed data will be stored.
 ///
 /// # Errors
 /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
 /// - If the decompression operation fails [`Error::DecompressionError`]
+/// - If the decompression operation fails and the data does not look like a Kraken stream [`ModuleError::PossiblyEncrypted`]
 ///
 /// # Safety
 /// - This function is unsafe because it calls the [`decompress`] function, which is unsafe.
-unsafe fn read_compressed_block(
-    reader: &mut BufReader<File>,
+#[cfg(not(feature = "rayon"))]
+unsafe fn read_compressed_block<R: Read + Seek>(
+    reader: &mut R,
     block: &ModuleBlockEntry,
     data: &mut [u8],
 ) -> Result<()> {
@@ -463,22 +1283,83 @@ unsafe fn read_compressed_block(
         &compressed_data,
         &mut decompressed_data,
         block.decompressed_size as usize,
-    )?;
+    )
+    .map_err(|error| check_encrypted(error, &compressed_data))?;
     data[block.decompressed_offset as usize
         ..(block.decompressed_offset + block.decompressed_size) as usize]
         .copy_from_slice(&decompressed_data);
     Ok(())
 }
 
+/// Decompresses blocks that have already been read from the file in parallel.
+///
+/// Each block is decompressed into its own range of `data`, starting at its
+/// [`decompressed_offset`](`ModuleBlockEntry::decompressed_offset`). As these ranges do not overlap, the output
+/// buffer is split into one slice per block before decompressing. This function requires the `rayon` feature.
+///
+/// # Arguments
+///
+/// * `blocks` - The blocks to decompress, along with their compressed data.
+/// * `data` - A mutable slice where the decompressed data will be stored.
+///
+/// # Errors
+/// - If the ranges of two blocks overlap or a block does not fit in `data` [`ModuleError::OverlappingBlocks`]
+/// - If the decompression operation fails [`Error::DecompressionError`]
+/// - If the decompression operation fails and the data does not look like a Kraken stream [`ModuleError::PossiblyEncrypted`]
+#[cfg(feature = "rayon")]
+fn decompress_blocks_parallel(
+    mut blocks: Vec<(&ModuleBlockEntry, Vec<u8>)>,
+    data: &mut [u8],
+) -> Result<()> {
+    blocks.sort_by_key(|(block, _)| block.decompressed_offset);
+
+    let mut jobs = Vec::with_capacity(blocks.len());
+    let mut remaining = data;
+    let mut remaining_offset = 0;
+    for (block, compressed_data) in blocks {
+        let start = block.decompressed_offset as usize;
+        let size = block.decompressed_size as usize;
+        if start < remaining_offset || start - remaining_offset + size > remaining.len() {
+            return Err(Error::ModuleError(ModuleError::OverlappingBlocks));
+        }
+        let (_, output) = mem::take(&mut remaining).split_at_mut(start - remaining_offset);
+        let (output, rest) = output.split_at_mut(size);
+        remaining = rest;
+        remaining_offset = start + size;
+        jobs.push((block, compressed_data, output));
+    }
+
+    jobs.into_par_iter()
+        .try_for_each(|(block, compressed_data, output)| {
+            let mut decompressed_data = vec![0u8; block.decompressed_size as usize];
+            unsafe {
+                decompress(
+                    &compressed_data,
+                    &mut decompressed_data,
+                    block.decompressed_size as usize,
+                )
+            }
+            .map_err(|error| check_encrypted(error, &compressed_data))?;
+            output.copy_from_slice(&decompressed_data);
+            Ok(())
+        })
+}
+
 /// Reads a single block of data from the file.
 ///
 /// This function is used when the file entry contains only one block of data.
 /// It reads the entire block, and then either copies it directly to the output
 /// if it's not compressed, or decompresses it if necessary.
 ///
+/// Whether the block is compressed is determined by the [`COMPRESSED`](`FileEntryFlags::COMPRESSED`) flag, as a Kraken
+/// stream can coincidentally have the same size as the decompressed data. Blocks where the sizes differ are always decompressed.
+///
+/// The block always starts exactly at `file_offset`. The alignment fields of [`ModuleFileEntry`]
+/// describe the layout of the sections inside the decompressed buffer, not padding before the data in the module.
+///
 /// # Arguments
 ///
-/// * `reader` - A mutable reference to a [`BufReader<File>`] from which to read the data.
+/// * `reader` - A mutable reference to a reader implementing [`Read`] and [`Seek`] from which to read the data.
 /// * `file_entry` - A reference to the [`ModuleFileEntry`] containing metadata about the file.
 /// * `file_offset` - The offset in the file where the data block starts.
 /// * `data` - A mutable reference to the [`Vec<u8>`] where the (decompressed) data will be stored.
@@ -486,11 +1367,13 @@ unsafe fn read_compressed_block(
 /// # Errors
 /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
 /// - If the decompression operation fails [`Error::DecompressionError`]
+/// - If the decompression operation fails and the data does not look like a Kraken stream [`ModuleError::PossiblyEncrypted`]
 ///
 /// # Safety
 /// - This function can be unsafe because it can call the [`decompress`] function, which is unsafe.
-fn read_single_block(
-    reader: &mut BufReader<File>,
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+fn read_single_block<R: Read + Seek>(
+    reader: &mut R,
     file_entry: &ModuleFileEntry,
     file_offset: u64,
     data: &mut Vec<u8>,
@@ -500,10 +1383,500 @@ fn read_single_block(
     let mut block = vec![0u8; compressed_size];
     reader.read_exact(&mut block)?;
 
-    if compressed_size == file_entry.total_uncompressed_size as usize {
-        data.copy_from_slice(&block);
+    let is_compressed = file_entry.flags.contains(FileEntryFlags::COMPRESSED)
+        || compressed_size != file_entry.total_uncompressed_size as usize;
+    if is_compressed {
+        unsafe {
+            decompress(&block, data, file_entry.total_uncompressed_size as usize)
+                .map_err(|error| check_encrypted(error, &block))?;
+        };
     } else {
-        unsafe { decompress(&block, data, file_entry.total_uncompressed_size as usize)? };
+        data.copy_from_slice(&block);
     }
     Ok(())
 }
+
+/// Replaces a failed decompression with [`ModuleError::PossiblyEncrypted`] if the compressed data does not look like Kraken data.
+///
+/// Every Oodle compressed stream (which Kraken is part of) starts with a header byte whose low nibble is `0xC`.
+/// Data failing to decompress without such a header is most likely encrypted, rather than being corrupted or
+/// hitting a bug in the reader.
+///
+/// # Arguments
+///
+/// * `error` - Error returned by [`decompress`].
+/// * `compressed_data` - Data that failed to be decompressed.
+fn check_encrypted(error: Error, compressed_data: &[u8]) -> Error {
+    let has_header = matches!(compressed_data.first(), Some(header) if header & 0x0F == 0x0C);
+    match error {
+        Error::DecompressionError(DecompressionError::DecompressionFailed(code)) if !has_header => {
+            Error::ModuleError(ModuleError::PossiblyEncrypted(code))
+        }
+        error => error,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::tag::{data_reference::TagDataReference, structure::TagStructType};
+
+    /// Creates a file entry that only has a tag group and ID, for tests that do not read any data.
+    pub(crate) fn entry(tag_group: &str, tag_id: i32) -> ModuleFileEntry {
+        ModuleFileEntry {
+            tag_group: tag_group.to_string(),
+            tag_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    /// Verifies that uncompressed single-block files are read from the exact file offset,
+    /// regardless of their section alignment.
+    fn test_read_single_block_ignores_alignment() {
+        let module = [0xAA, 0xBB, 0xCC, 0x01, 0x02, 0x03, 0x04, 0xDD];
+        let entry = ModuleFileEntry {
+            total_compressed_size: 4,
+            total_uncompressed_size: 4,
+            header_alignment: 4,
+            tag_data_alignment: 4,
+            ..Default::default()
+        };
+
+        let mut reader = Cursor::new(module.to_vec());
+        let mut data = vec![0u8; 4];
+        read_single_block(&mut reader, &entry, 3, &mut data).unwrap();
+        assert_eq!(data, [0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    /// Verifies that single blocks flagged as compressed are decompressed even if the sizes match.
+    fn test_read_single_block_equal_sizes_compressed() {
+        let module = [0x01, 0x02, 0x03, 0x04];
+        let entry = ModuleFileEntry {
+            flags: FileEntryFlags::COMPRESSED,
+            total_compressed_size: 4,
+            total_uncompressed_size: 4,
+            ..Default::default()
+        };
+
+        let mut reader = Cursor::new(module.to_vec());
+        let mut data = vec![0u8; 4];
+        // Data is not a Kraken stream, so decompression must be attempted and fail instead of the data being copied.
+        assert!(read_single_block(&mut reader, &entry, 0, &mut data).is_err());
+        assert_eq!(data, [0x00; 4]);
+    }
+
+    #[test]
+    /// Verifies that the actual resource alignment is reported in bytes, and is not applied when splitting sections.
+    fn test_actual_resource_alignment() {
+        let mut entry = ModuleFileEntry {
+            uncompressed_resource_data_size: 2,
+            uncompressed_actual_resource_size: 4,
+            actual_resource_data_alignment: 4,
+            ..Default::default()
+        };
+        assert_eq!(entry.actual_resource_alignment(), Some(16));
+
+        let sections = entry.split_data(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(sections.resource_data, [1, 2]);
+        assert_eq!(sections.actual_resource, [3, 4, 5, 6]);
+
+        entry.actual_resource_data_alignment = 64;
+        assert_eq!(entry.actual_resource_alignment(), None);
+    }
+
+    fn datablock(section_type: TagSectionType, offset: u64, entry_size: u32) -> TagDataBlock {
+        let mut block = TagDataBlock::default();
+        block.section_type = section_type;
+        block.offset = offset;
+        block.entry_size = entry_size;
+        block
+    }
+
+    fn data_reference(field_offset: u32, target_index: i32) -> TagDataReference {
+        let mut reference = TagDataReference::default();
+        reference.field_block = 0;
+        reference.field_offset = field_offset;
+        reference.target_index = target_index;
+        reference
+    }
+
+    #[test]
+    /// Verifies that `FieldData` bytes are read from the section of their referenced datablock.
+    fn test_read_field_data_sections() {
+        // Header (0x10), tag data (0x30), resource data (0x10), actual resource data (0x10).
+        let mut buffer = vec![0u8; 0x60];
+        buffer[0x10 + 0x14] = 8;
+        buffer[0x10 + 0x2C] = 8;
+        buffer[0x44..0x4C].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        buffer[0x50..0x58].copy_from_slice(&[9, 10, 11, 12, 13, 14, 15, 16]);
+
+        let mut reader = BufReader::new(Cursor::new(buffer[0x10..0x40].to_vec()));
+        let mut resource_data = FieldData::default();
+        resource_data.read(&mut reader).unwrap();
+        let mut actual_resource = FieldData::default();
+        actual_resource.read(&mut reader).unwrap();
+        let mut unreferenced = FieldData::default();
+        unreferenced.field_offset = 0x2;
+
+        let mut tag_info = TagFile {
+            datablock_definitions: vec![
+                datablock(TagSectionType::TagData, 0, 0x30),
+                datablock(TagSectionType::ResourceData, 0x4, 0x8),
+                datablock(TagSectionType::ActualResource, 0, 0x8),
+            ],
+            data_references: vec![data_reference(0, 1), data_reference(0x18, 2)],
+            ..Default::default()
+        };
+        tag_info.header.header_size = 0x10;
+        tag_info.header.data_size = 0x30;
+        tag_info.header.resource_size = 0x10;
+
+        let entry = ModuleFileEntry {
+            data_stream: Some(BufReader::new(Cursor::new(buffer))),
+            tag_info: Some(tag_info),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            entry.read_field_data(&resource_data).unwrap(),
+            Some(&[1, 2, 3, 4, 5, 6, 7, 8][..])
+        );
+        assert_eq!(
+            entry.read_field_data(&actual_resource).unwrap(),
+            Some(&[9, 10, 11, 12, 13, 14, 15, 16][..])
+        );
+        assert_eq!(entry.read_field_data(&unreferenced).unwrap(), None);
+    }
+
+    #[test]
+    /// Verifies that data references whose end overflows are reported as out of bounds instead of panicking.
+    fn test_read_field_data_overflow() {
+        // Both the start of the referenced datablock and the end of the field overflow.
+        for (offset, size) in [(u64::MAX, 0), (u64::MAX - 0x28, 0x8)] {
+            let mut tag_info = TagFile {
+                datablock_definitions: vec![
+                    datablock(TagSectionType::TagData, 0, 0x18),
+                    datablock(TagSectionType::ResourceData, offset, size),
+                ],
+                data_references: vec![data_reference(0, 1)],
+                ..Default::default()
+            };
+            tag_info.header.header_size = 0x10;
+            tag_info.header.data_size = 0x18;
+            let entry = ModuleFileEntry {
+                data_stream: Some(BufReader::new(Cursor::new(vec![0u8; 0x28]))),
+                tag_info: Some(tag_info),
+                ..Default::default()
+            };
+            let mut field = FieldData::default();
+            field.size = size;
+            assert!(matches!(
+                entry.read_field_data(&field),
+                Err(Error::TagError(TagError::DataOutOfBounds))
+            ));
+        }
+    }
+
+    #[test]
+    /// Verifies that decompression failures are only reported as encryption when the data has no Kraken header.
+    fn test_check_encrypted() {
+        let failed = || Error::DecompressionError(DecompressionError::DecompressionFailed(-1));
+        assert!(matches!(
+            check_encrypted(failed(), &[0x3F, 0xA2, 0x11]),
+            Error::ModuleError(ModuleError::PossiblyEncrypted(-1))
+        ));
+        assert!(matches!(
+            check_encrypted(failed(), &[0x8C, 0x06, 0x11]),
+            Error::DecompressionError(DecompressionError::DecompressionFailed(-1))
+        ));
+        assert!(matches!(
+            check_encrypted(
+                Error::DecompressionError(DecompressionError::BufferSizeOverflow),
+                &[0x3F]
+            ),
+            Error::DecompressionError(DecompressionError::BufferSizeOverflow)
+        ));
+    }
+
+    #[test]
+    /// Verifies that the sections of a file concatenate back to its full data.
+    fn test_split_sections() {
+        let data: Vec<u8> = (0..10).collect();
+        let mut entry = ModuleFileEntry {
+            uncompressed_header_size: 4,
+            uncompressed_tag_data_size: 3,
+            uncompressed_resource_data_size: 0,
+            uncompressed_actual_resource_size: 3,
+            data_stream: Some(BufReader::new(Cursor::new(data.clone()))),
+            ..Default::default()
+        };
+
+        let sections = entry.split_sections().unwrap();
+        assert_eq!(sections.header, [0, 1, 2, 3]);
+        assert_eq!(
+            [
+                sections.header,
+                sections.tag_data,
+                sections.resource_data,
+                sections.actual_resource
+            ]
+            .concat(),
+            data
+        );
+
+        entry.uncompressed_actual_resource_size = 4;
+        assert!(entry.split_sections().is_err());
+    }
+
+    #[test]
+    /// Verifies that the `AnyTag` is read from the start of the main struct without consuming the data stream.
+    fn test_read_any_tag() {
+        let mut buffer = vec![0u8; 0x10];
+        buffer.extend_from_slice(&[0xFF; 8]); // vtable space
+        buffer.extend_from_slice(&0x1234_5678i32.to_le_bytes());
+        buffer.extend_from_slice(&7i32.to_le_bytes());
+
+        let mut main_struct = TagStruct::default();
+        main_struct.struct_type = TagStructType::MainStruct;
+        let mut tag_info = TagFile {
+            datablock_definitions: vec![datablock(TagSectionType::TagData, 0x4, 0x10)],
+            struct_definitions: vec![main_struct],
+            ..Default::default()
+        };
+        tag_info.header.header_size = 0xC;
+
+        let mut entry = ModuleFileEntry {
+            data_stream: Some(BufReader::new(Cursor::new(buffer))),
+            ..Default::default()
+        };
+        assert!(matches!(
+            entry.read_any_tag(),
+            Err(Error::TagError(TagError::NoTagInfo))
+        ));
+
+        entry.tag_info = Some(tag_info);
+        let any_tag = entry.read_any_tag().unwrap();
+        assert_eq!(any_tag.internal_struct.tag_id, 0x1234_5678);
+        assert_eq!(any_tag.internal_struct.local_tag_handle, 7);
+        assert_eq!(
+            entry
+                .data_stream
+                .as_mut()
+                .unwrap()
+                .stream_position()
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    /// Verifies that deferring resources only reads the blocks of the header and tag data, and that the remaining
+    /// blocks are loaded on demand without moving the data stream.
+    fn test_defer_resources() {
+        let mut entry = ModuleFileEntry {
+            flags: FileEntryFlags::RAW_FILE,
+            block_count: 2,
+            total_compressed_size: 12,
+            total_uncompressed_size: 12,
+            uncompressed_header_size: 4,
+            uncompressed_tag_data_size: 4,
+            uncompressed_resource_data_size: 4,
+            ..Default::default()
+        };
+        let block = |offset: u32, size: u32| ModuleBlockEntry {
+            compressed_offset: offset,
+            compressed_size: size,
+            decompressed_offset: offset,
+            decompressed_size: size,
+            is_compressed: false,
+        };
+        let blocks = [block(0, 8), block(8, 4)];
+        let mut reader = Cursor::new((1..=12).collect::<Vec<u8>>());
+
+        entry
+            .read_tag(&mut reader, 0, &blocks, &ModuleVersion::Season3, true)
+            .unwrap();
+        assert!(entry.resources_deferred());
+        let stream = entry.data_stream.as_mut().unwrap();
+        assert_eq!(
+            stream.get_ref().get_ref()[..],
+            [1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0]
+        );
+        stream.seek(SeekFrom::Start(2)).unwrap();
+
+        entry
+            .load_deferred_resources(&mut reader, 0, &blocks)
+            .unwrap();
+        assert!(!entry.resources_deferred());
+        let stream = entry.data_stream.as_mut().unwrap();
+        assert_eq!(stream.stream_position().unwrap(), 2);
+        assert_eq!(
+            stream.get_ref().get_ref()[..],
+            (1..=12).collect::<Vec<u8>>()
+        );
+    }
+
+    #[test]
+    /// Verifies that each block is read from its own compressed offset after the start of the file data, skipping gaps
+    /// between blocks and the data of other files.
+    fn test_read_non_contiguous_blocks() {
+        let mut entry = ModuleFileEntry {
+            flags: FileEntryFlags::RAW_FILE,
+            data_offset: 0x10,
+            block_count: 2,
+            total_compressed_size: 12,
+            total_uncompressed_size: 8,
+            uncompressed_header_size: 8,
+            ..Default::default()
+        };
+        let blocks = [
+            ModuleBlockEntry {
+                compressed_offset: 0,
+                compressed_size: 4,
+                decompressed_offset: 0,
+                decompressed_size: 4,
+                is_compressed: false,
+            },
+            ModuleBlockEntry {
+                compressed_offset: 8,
+                compressed_size: 4,
+                decompressed_offset: 4,
+                decompressed_size: 4,
+                is_compressed: false,
+            },
+        ];
+        let mut module = vec![0xEE; 0x30];
+        module.extend_from_slice(&[1, 2, 3, 4, 0xFF, 0xFF, 0xFF, 0xFF, 5, 6, 7, 8]);
+        let mut reader = Cursor::new(module);
+
+        entry
+            .read_tag(&mut reader, 0x20, &blocks, &ModuleVersion::Season3, false)
+            .unwrap();
+        assert_eq!(
+            entry.data_stream.unwrap().get_ref().get_ref()[..],
+            [1, 2, 3, 4, 5, 6, 7, 8]
+        );
+    }
+
+    #[test]
+    /// Verifies that the offset and flags are split from the raw data offset at the position used by each version.
+    fn test_data_offset_per_version() {
+        for (version, position) in [
+            (ModuleVersion::Flight1, 0x18),
+            (ModuleVersion::Release, 0x10),
+            (ModuleVersion::CampaignFlight, 0x10),
+            (ModuleVersion::Season3, 0x10),
+        ] {
+            let mut data = vec![0; 0x58];
+            data[position..position + 8].copy_from_slice(&0x0003_8765_4321_0FEDu64.to_le_bytes());
+            let mut reader = BufReader::new(Cursor::new(data));
+            let mut entry = ModuleFileEntry::default();
+            entry.read(&mut reader, &version).unwrap();
+
+            assert_eq!(entry.data_offset, 0x8765_4321_0FED);
+            assert_eq!(
+                entry.data_offset_flags,
+                DataOffsetType::USE_HD1 | DataOffsetType::DEBUG
+            );
+            assert_eq!(reader.stream_position().unwrap(), 0x58);
+        }
+    }
+
+    #[test]
+    /// Verifies that data which does not start with a tag header is rejected.
+    fn test_from_bytes_invalid_magic() {
+        assert!(matches!(
+            ModuleFileEntry::from_bytes("mat ", vec![0; 0x80]),
+            Err(Error::TagError(TagError::IncorrectMagic(0)))
+        ));
+    }
+
+    #[test]
+    /// Verifies that the whole stream is copied from its start, including data larger than the copy buffer.
+    fn test_copy_to() {
+        let mut entry = ModuleFileEntry::default();
+        assert!(matches!(
+            entry.copy_to(&mut Vec::new()),
+            Err(Error::TagError(TagError::NotLoaded))
+        ));
+
+        #[allow(clippy::cast_possible_truncation)]
+        let data: Vec<u8> = (0..COPY_BUFFER_SIZE + 0x10).map(|i| i as u8).collect();
+        entry.data_stream = Some(BufReader::new(Cursor::new(data.clone())));
+        entry
+            .data_stream
+            .as_mut()
+            .unwrap()
+            .seek(SeekFrom::Start(0x10))
+            .unwrap();
+
+        let mut output = Vec::new();
+        assert_eq!(entry.copy_to(&mut output).unwrap(), data.len() as u64);
+        assert_eq!(output, data);
+    }
+
+    #[test]
+    /// Verifies that the decompressed length is only available once the file is loaded.
+    fn test_decompressed_len() {
+        let mut entry = ModuleFileEntry::default();
+        assert_eq!(entry.decompressed_len(), None);
+
+        entry.data_stream = Some(BufReader::new(Cursor::new(vec![0; 0x30])));
+        entry
+            .data_stream
+            .as_mut()
+            .unwrap()
+            .seek(SeekFrom::Start(0x10))
+            .unwrap();
+        assert_eq!(entry.decompressed_len(), Some(0x30));
+    }
+
+    #[derive(Default)]
+    /// Structure implementing only the required methods of [`TagStructure`], as a hand-written implementation would.
+    struct HandWritten(i32);
+
+    impl TagStructure for HandWritten {
+        fn size(&mut self) -> u64 {
+            4
+        }
+
+        fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+            self.0 = reader.read_i32::<LE>()?;
+            Ok(())
+        }
+
+        fn offsets(&self) -> HashMap<&'static str, u64> {
+            HashMap::from([("value", 0)])
+        }
+
+        fn load_field_blocks<R: BufReaderExt>(
+            &mut self,
+            _source_index: i32,
+            _adjusted_base: u64,
+            _reader: &mut R,
+            _structs: &[TagStruct],
+            _blocks: &[TagDataBlock],
+            _version: &ModuleVersion,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    /// Verifies that the provided methods of [`TagStructure`] work for implementations without the derive macro.
+    fn test_tag_structure_defaults() {
+        let mut structure = HandWritten::default();
+        let mut reader = Cursor::new(7i32.to_le_bytes());
+        structure
+            .read_versioned(&mut reader, &ModuleVersion::Flight1)
+            .unwrap();
+        assert_eq!(structure.0, 7);
+        assert_eq!(structure.offsets_sorted(), [("value", 0)]);
+        assert_eq!(structure.field_at_offset(0), Some("value"));
+        assert_eq!(structure.field_at_offset(4), None);
+        assert!(structure.references().is_empty());
+        assert_eq!(structure.element_count(), 0);
+    }
+}
@@ -2,14 +2,16 @@
 
 use byteorder::{ReadBytesExt, LE};
 use num_enum::TryFromPrimitive;
-use std::{fs::File, io::BufReader};
 
 use crate::common::errors::{Error, ModuleError};
+use crate::common::extensions::BufReaderExt;
+use crate::common::logging;
 use crate::Result;
 
 const HEADER_MAGIC: u32 = 0x6468_6F6D; // "mohd"
 
-#[derive(Default, Debug, PartialEq, Eq, TryFromPrimitive, PartialOrd, Ord)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, TryFromPrimitive, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i32)]
 /// Revision number of a module file.
 /// This version number determines how tags should be read.
@@ -33,6 +35,10 @@ pub struct ModuleHeader {
     /// Revision number of the module.
     /// This determines how offsets are calculated and if tag names should be read.
     pub version: ModuleVersion,
+    /// Version number as stored in the module file.
+    /// Only differs from [`version`](`ModuleHeader::version`) if an unknown version was read with
+    /// [`allow_unknown_version`](`crate::module::builder::ModuleFileBuilder::allow_unknown_version`) enabled.
+    pub raw_version: i32,
     /// Unique identifier of module.
     pub module_id: i64,
     /// Number of files in the module.
@@ -44,15 +50,16 @@ pub struct ModuleHeader {
     /// Index of `resourcemetadata` tag, which contains info on how resources should be loaded.
     resourcemetadata_index: i32,
     /// Index of the first resource entry ([`file_count`](`ModuleHeader::file_count`) - [`resource_count`](`ModuleHeader::resource_count`)).
-    resource_index: i32,
+    pub(super) resource_index: i32,
     /// Total size in bytes of the string table.
     pub(super) strings_size: u32,
     /// Number of resource files.
     pub(super) resource_count: u32,
     /// Number of data blocks.
     pub(super) block_count: u32,
-    /// Same between modules, changes per build?
-    build_version: u64,
+    /// Identifies the build of the game that produced the module.
+    /// Same between all modules of a build, and changes with every patch.
+    pub(super) build_version: u64,
     /// If non-zero, requires hd1 file.
     pub(super) hd1_delta: u64,
     /// Total size of packed data in the module.
@@ -67,19 +74,34 @@ impl ModuleHeader {
     /// Reads the module header from the given buffered reader.
     /// # Arguments
     ///
-    /// * `reader` - A mutable reference to a [`BufReader<File>`] from which to read the data.
+    /// * `reader` - A mutable reference to a reader implementing [`BufReaderExt`] from which to read the data.
+    /// * `allow_unknown_version` - Whether versions newer than [`ModuleVersion::Season3`] should be read as [`ModuleVersion::Season3`] instead of failing.
     ///
     /// # Errors
     /// - If the magic number is not equal to [`HEADER_MAGIC`] [`ModuleError::IncorrectMagic`]
     /// - If the version number is not recognized [`ModuleError::IncorrectVersion`]
     /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
-    pub(super) fn read(&mut self, reader: &mut BufReader<File>) -> Result<()> {
+    pub(super) fn read<R: BufReaderExt>(
+        &mut self,
+        reader: &mut R,
+        allow_unknown_version: bool,
+    ) -> Result<()> {
         self.magic = reader.read_u32::<LE>()?;
         if self.magic != HEADER_MAGIC {
             return Err(Error::ModuleError(ModuleError::IncorrectMagic(self.magic)));
         }
-        self.version = ModuleVersion::try_from_primitive(reader.read_i32::<LE>()?)
-            .map_err(ModuleError::IncorrectVersion)?;
+        self.raw_version = reader.read_i32::<LE>()?;
+        self.version = match ModuleVersion::try_from_primitive(self.raw_version) {
+            Ok(version) => version,
+            Err(_) if allow_unknown_version && self.raw_version > ModuleVersion::Season3 as i32 => {
+                logging::warn!(
+                    version = self.raw_version,
+                    "unknown module version, reading as Season3"
+                );
+                ModuleVersion::Season3
+            }
+            Err(error) => return Err(Error::ModuleError(ModuleError::IncorrectVersion(error))),
+        };
 
         self.module_id = reader.read_i64::<LE>()?;
         self.file_count = reader.read_u32::<LE>()?;
@@ -99,3 +121,36 @@ impl ModuleHeader {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn header(version: i32) -> Vec<u8> {
+        let mut data = HEADER_MAGIC.to_le_bytes().to_vec();
+        data.extend_from_slice(&version.to_le_bytes());
+        data.resize(0x50, 0);
+        data
+    }
+
+    #[test]
+    /// Verifies that unknown newer versions are only accepted when allowed, and read as Season 3.
+    fn test_allow_unknown_version() {
+        let mut module_header = ModuleHeader::default();
+        assert!(matches!(
+            module_header.read(&mut Cursor::new(header(54)), false),
+            Err(Error::ModuleError(ModuleError::IncorrectVersion(_)))
+        ));
+
+        module_header
+            .read(&mut Cursor::new(header(54)), true)
+            .unwrap();
+        assert_eq!(module_header.version, ModuleVersion::Season3);
+        assert_eq!(module_header.raw_version, 54);
+
+        assert!(module_header
+            .read(&mut Cursor::new(header(50)), true)
+            .is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Summary of the contents of a module, built from its parsed headers.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use super::{file::DataOffsetType, header::ModuleVersion, loader::ModuleFile};
+
+#[derive(Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Summary of a module, returned by [`ModuleFile::inventory`].
+///
+/// All values come from data parsed when the module is opened, so no tags are read or decompressed.
+/// The [`Display`] implementation prints a human-readable report, and the summary can be serialized with the `serde` feature.
+pub struct ModuleInventory {
+    /// Revision of the module.
+    pub version: ModuleVersion,
+    /// Unique identifier of the module.
+    pub module_id: i64,
+    /// Number of file entries in the module, including resources.
+    pub file_count: usize,
+    /// Number of file entries with a tag ID.
+    pub tag_count: usize,
+    /// Number of resources listed in [`resource_indices`](`ModuleFile::resource_indices`).
+    pub resource_count: usize,
+    /// Number of file entries for each tag group, ordered by group. Resources are not counted.
+    pub groups: BTreeMap<String, usize>,
+    /// Sum of the compressed sizes of all files, in bytes.
+    pub total_compressed_size: u64,
+    /// Sum of the uncompressed sizes of all files, in bytes.
+    pub total_uncompressed_size: u64,
+    /// Whether the module stores part of its data in an HD1 file.
+    pub requires_hd1: bool,
+    /// Number of files stored in the HD1 file.
+    pub hd1_file_count: usize,
+}
+
+impl ModuleInventory {
+    /// Builds the summary of a module.
+    pub(super) fn new(module: &ModuleFile) -> Self {
+        let mut inventory = Self {
+            version: module.header.version.clone(),
+            module_id: module.module_id(),
+            file_count: module.files.len(),
+            resource_count: module.resource_indices.len(),
+            requires_hd1: module.requires_hd1(),
+            ..Default::default()
+        };
+        for file in &module.files {
+            inventory.total_compressed_size += u64::from(file.total_compressed_size);
+            inventory.total_uncompressed_size += u64::from(file.total_uncompressed_size);
+            if file.data_offset_flags.contains(DataOffsetType::USE_HD1) {
+                inventory.hd1_file_count += 1;
+            }
+            if file.tag_id != -1 {
+                inventory.tag_count += 1;
+                *inventory.groups.entry(file.tag_group.clone()).or_default() += 1;
+            }
+        }
+        inventory
+    }
+}
+
+impl Display for ModuleInventory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "Module ID:         {:#018X}", self.module_id)?;
+        writeln!(f, "Version:           {:?}", self.version)?;
+        writeln!(
+            f,
+            "Files:             {} ({} tags, {} resources)",
+            self.file_count, self.tag_count, self.resource_count
+        )?;
+        writeln!(f, "Compressed size:   {} bytes", self.total_compressed_size)?;
+        writeln!(
+            f,
+            "Uncompressed size: {} bytes",
+            self.total_uncompressed_size
+        )?;
+        if self.requires_hd1 {
+            writeln!(
+                f,
+                "HD1:               required ({} files)",
+                self.hd1_file_count
+            )?;
+        } else {
+            writeln!(f, "HD1:               not required")?;
+        }
+        writeln!(f, "Groups:")?;
+        for (group, count) in &self.groups {
+            writeln!(f, "    {group:<4}  {count:>6}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::file::{tests::entry, ModuleFileEntry};
+
+    fn sized_entry(tag_group: &str, tag_id: i32, size: u32) -> ModuleFileEntry {
+        let mut entry = entry(tag_group, tag_id);
+        entry.total_compressed_size = size;
+        entry.total_uncompressed_size = size * 2;
+        entry
+    }
+
+    #[test]
+    /// Verifies that files are counted per group and that sizes are summed, with resources excluded from groups.
+    fn test_inventory() {
+        let mut hd1_entry = sized_entry("bitm", 3, 0x100);
+        hd1_entry.data_offset_flags = DataOffsetType::USE_HD1;
+        let mut module = ModuleFile::default();
+        module.files = vec![
+            sized_entry("mat ", 1, 0x10),
+            sized_entry("mat ", 2, 0x20),
+            hd1_entry,
+            sized_entry("", -1, 0x40),
+        ];
+        module.resource_indices = vec![3];
+        module.header.hd1_delta = 0x1000;
+
+        let inventory = module.inventory();
+        assert_eq!(inventory.file_count, 4);
+        assert_eq!(inventory.tag_count, 3);
+        assert_eq!(inventory.resource_count, 1);
+        assert_eq!(inventory.groups.get("mat "), Some(&2));
+        assert_eq!(inventory.groups.get("bitm"), Some(&1));
+        assert_eq!(inventory.groups.len(), 2);
+        assert_eq!(inventory.total_compressed_size, 0x170);
+        assert_eq!(inventory.total_uncompressed_size, 0x2E0);
+        assert!(inventory.requires_hd1);
+        assert_eq!(inventory.hd1_file_count, 1);
+
+        let report = inventory.to_string();
+        assert!(report.contains("Files:             4 (3 tags, 1 resources)"));
+        assert!(report.contains("HD1:               required (1 files)"));
+        assert!(report.contains("    mat        2"));
+    }
+}
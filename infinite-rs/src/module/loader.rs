@@ -2,22 +2,35 @@
 
 use byteorder::{ReadBytesExt, LE};
 use std::{
-    fs::File,
-    io::{BufReader, Seek, SeekFrom},
-    path::Path,
+    collections::HashMap,
+    fs::{create_dir_all, write, File},
+    io::{BufRead, BufReader, Cursor, Read, Result as IoResult, Seek, SeekFrom},
+    path::{Path, PathBuf},
     ptr::eq,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
 };
 
 use super::{
     block::ModuleBlockEntry,
-    file::{DataOffsetType, ModuleFileEntry},
+    builder::ModuleFileBuilder,
+    diff::ModuleDiff,
+    file::{DataOffsetType, LoadedTag, ModuleFileEntry, ModuleFileMeta, TagStructure},
     header::{ModuleHeader, ModuleVersion},
+    inventory::ModuleInventory,
 };
-use crate::Result;
 use crate::{
-    common::{errors::TagError, extensions::BufReaderExt},
+    common::{
+        errors::{ModuleError, TagError},
+        extensions::{BufReaderExt, Enumerable},
+        logging,
+    },
+    tag::{loader::TagFile, types::common_types::FieldTagResource},
     Error,
 };
+use crate::{FileIndex, Result, TagId};
+
+/// Handle to an HD1 file that can be shared between modules, see [`ModuleFileBuilder::shared_hd1`].
+pub type SharedHd1 = Arc<Mutex<BufReader<File>>>;
 
 #[derive(Default, Debug)]
 /// Module structure which contains the layout of the entire module file.
@@ -25,6 +38,12 @@ pub struct ModuleFile {
     /// Information relating to how the other fields should be read.
     pub header: ModuleHeader,
     /// Metadata regarding compression and layout of files (tags).
+    ///
+    /// Entries are stored in the order they appear on disk. This vector must never be reordered in place, as
+    /// indices into it are used as references throughout the module (for instance [`parent_index`](`ModuleFileEntry::parent_index`),
+    /// [`resource_index`](`ModuleFileEntry::resource_index`) and [`resource_indices`](`ModuleFile::resource_indices`)).
+    /// Use [`sorted_indices_by_group`](`ModuleFile::sorted_indices_by_group`) or [`sorted_indices_by_id`](`ModuleFile::sorted_indices_by_id`)
+    /// to iterate in a different order.
     pub files: Vec<ModuleFileEntry>,
     /// Indices of resource files present in the module.
     pub resource_indices: Vec<u32>,
@@ -33,15 +52,23 @@ pub struct ModuleFile {
     /// Offset in [`BufReader`] where file data starts.
     file_data_offset: u64,
     /// Reference to the module file buffer.
-    module_file: Option<BufReader<File>>,
-    /// Reference to HD1 buffer if it exists.
-    hd1_file: Option<BufReader<File>>,
+    module_file: Option<ModuleSource>,
+    /// Reference to HD1 buffer if it exists, possibly shared with other modules.
+    hd1_file: Option<SharedHd1>,
+    /// Path of the module file, used to reopen it in [`read_tag_owned`](`ModuleFile::read_tag_owned`).
+    file_path: Option<PathBuf>,
+    /// Path of the HD1 file if it has been opened.
+    hd1_path: Option<PathBuf>,
     /// Whether to use the HD1 module or not.
     pub use_hd1: bool,
+    /// Configuration used when reading the module, see [`ModuleFileBuilder`].
+    pub(super) config: ModuleFileBuilder,
 }
 
 impl ModuleFile {
     /// Instantiates a [`ModuleFile`] object from the given file path.
+    ///
+    /// To configure how the module is read, use [`ModuleFileBuilder`].
     pub fn from_path<T: AsRef<Path>>(file_path: T) -> Result<Self> {
         let mut module = Self::default();
         module.read(file_path)?;
@@ -61,10 +88,60 @@ impl ModuleFile {
     /// - If the string table has invalid UTF-8 [`Utf8ReadingError`](`crate::Error::Utf8ReadingError`)
     pub fn read<T: AsRef<Path>>(&mut self, file_path: T) -> Result<()> {
         let file = File::open(&file_path)?;
-        let mut reader = BufReader::new(file);
+        self.file_path = Some(file_path.as_ref().to_path_buf());
+        self.read_source(ModuleSource::File(BufReader::new(file)))?;
+        self.open_hd1(file_path)
+    }
+
+    /// Reads the module from a buffer in memory.
+    ///
+    /// This is equivalent to [`read`](`ModuleFile::read`), but does not require the module to be stored on disk,
+    /// for instance when the module has been downloaded or provided by the user in a browser. The buffer is kept
+    /// for subsequent [`read_tag`](`ModuleFile::read_tag`) calls. HD1 files are not supported for modules read from memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The contents of the module file.
+    ///
+    /// # Errors
+    /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    /// - If the string table has invalid UTF-8 [`Utf8ReadingError`](`crate::Error::Utf8ReadingError`)
+    pub fn read_from_slice(&mut self, data: Vec<u8>) -> Result<()> {
+        self.read_source(ModuleSource::Memory(Cursor::new(data)))
+    }
 
-        self.header.read(&mut reader)?;
-        self.open_hd1(file_path)?;
+    /// Clears the module so that it can be reused to read another module.
+    ///
+    /// All parsed state is dropped and open files are closed, but the allocated capacity of [`files`](`ModuleFile::files`),
+    /// [`resource_indices`](`ModuleFile::resource_indices`) and the block table is kept, so that tools reading many modules
+    /// in a row do not need to reallocate them for every module. The configuration the module was built with is kept as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use infinite_rs::ModuleFile;
+    ///
+    /// let mut module = ModuleFile::from_path("C:/XboxGames/Halo Infinite/Content/deploy/any/globals-rtx-new.module").unwrap();
+    /// module.clear();
+    /// module.read("C:/XboxGames/Halo Infinite/Content/deploy/any/globals_tags-rtx-new.module").unwrap();
+    /// ```
+    pub fn clear(&mut self) {
+        self.header = ModuleHeader::default();
+        self.files.clear();
+        self.resource_indices.clear();
+        self.blocks.clear();
+        self.file_data_offset = 0;
+        self.module_file = None;
+        self.hd1_file = None;
+        self.file_path = None;
+        self.hd1_path = None;
+        self.use_hd1 = false;
+    }
+
+    /// Reads the structure of the module from the given source and stores the source for reading tags.
+    fn read_source(&mut self, mut reader: ModuleSource) -> Result<()> {
+        self.header
+            .read(&mut reader, self.config.allow_unknown_version)?;
 
         for _ in 0..self.header.file_count {
             let mut file = ModuleFileEntry::default();
@@ -76,11 +153,41 @@ impl ModuleFile {
         reader.seek(SeekFrom::Start(
             strings_offset + u64::from(self.header.strings_size),
         ))?;
-        self.resource_indices = (0..self.header.resource_count)
-            .map(|_| -> Result<u32> { Ok(reader.read_u32::<LE>()?) })
-            .collect::<Result<Vec<_>>>()?;
+        for _ in 0..self.header.resource_count {
+            self.resource_indices.push(reader.read_u32::<LE>()?);
+        }
         let post_resource_offset = reader.stream_position()?;
 
+        if !self.config.skip_tag_names {
+            self.read_tag_names(&mut reader, strings_offset)?;
+        }
+
+        reader.seek(SeekFrom::Start(post_resource_offset))?;
+        for _ in 0..self.header.block_count {
+            let mut block = ModuleBlockEntry::default();
+            block.read(&mut reader)?;
+            self.blocks.push(block);
+        }
+
+        // Align to 0x?????000
+        let stream_position = reader.stream_position()?;
+        reader.seek(SeekFrom::Start((stream_position / 0x1000 + 1) * 0x1000))?;
+        self.file_data_offset = reader.stream_position()?;
+        self.module_file = Some(reader);
+        Ok(())
+    }
+
+    /// Assigns [`tag_name`](`ModuleFileEntry::tag_name`) to each file entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A mutable reference to a reader implementing [`BufReaderExt`] from which to read the string table.
+    /// * `strings_offset` - Offset of the string table in the module file.
+    fn read_tag_names<R: BufReaderExt>(
+        &mut self,
+        reader: &mut R,
+        strings_offset: u64,
+    ) -> Result<()> {
         // Read strings contained in the file. A stringlist only exists in files before Season 3.
         // Each entry is separated by a null terminator, and files specify their offset themselves
         // in no particular order, so we cannot pre-read and just index into them.
@@ -103,27 +210,31 @@ impl ModuleFile {
                 file.tag_name = tag_path;
             }
         }
-
-        reader.seek(SeekFrom::Start(post_resource_offset))?;
-        self.blocks =
-            reader.read_enumerable::<ModuleBlockEntry>(u64::from(self.header.block_count))?;
-
-        // Align to 0x?????000
-        let stream_position = reader.stream_position()?;
-        reader.seek(SeekFrom::Start((stream_position / 0x1000 + 1) * 0x1000))?;
-        self.file_data_offset = reader.stream_position()?;
-        self.module_file = Some(reader);
         Ok(())
     }
 
     /// Opens the HD1 file if it exists.
     fn open_hd1<T: AsRef<Path>>(&mut self, file_path: T) -> Result<()> {
+        if self.config.disable_hd1 {
+            return Ok(());
+        }
         if self.header.hd1_delta != 0 {
-            let hd1 = file_path.as_ref().join("_hd1");
+            if let Some(hd1_file) = &self.config.shared_hd1 {
+                self.use_hd1 = true;
+                self.hd1_file = Some(Arc::clone(hd1_file));
+                self.hd1_path.clone_from(&self.config.hd1_path);
+                return Ok(());
+            }
+            let hd1 = self
+                .config
+                .hd1_path
+                .clone()
+                .unwrap_or_else(|| file_path.as_ref().join("_hd1"));
             if hd1.exists() {
                 self.use_hd1 = true;
-                let file = File::open(hd1)?;
-                self.hd1_file = Some(BufReader::new(file));
+                let file = File::open(&hd1)?;
+                self.hd1_file = Some(Arc::new(Mutex::new(BufReader::new(file))));
+                self.hd1_path = Some(hd1);
             }
         }
         Ok(())
@@ -140,20 +251,30 @@ impl ModuleFile {
     ///
     /// # Returns
     /// Returns the tag path of the file entry if the operation is successful.
+    ///
+    /// # Errors
+    /// - If the parent or resource indices of the file point outside of the module [`ModuleError::InvalidIndex`]
+    /// - If the recursion depth exceeds 3 [`TagError::RecursionDepth`]
     fn get_tag_path(&self, index: usize, depth: usize) -> Result<String> {
         if depth > 3 {
             return Err(Error::TagError(TagError::RecursionDepth));
         }
         let file = &self.files[index];
         if file.tag_id == -1 && file.parent_index != -1 {
-            let parent = &self.files[usize::try_from(file.parent_index)?];
+            let parent = self
+                .files
+                .get(usize::try_from(file.parent_index)?)
+                .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
             let mut parent_name: String = String::new();
-            let child_index = self.resource_indices[usize::try_from(parent.resource_index)?
-                ..usize::try_from(parent.resource_index)?
-                    + usize::try_from(parent.resource_count)?]
+            let resource_start = usize::try_from(parent.resource_index)?;
+            let resource_end = resource_start + usize::try_from(parent.resource_count)?;
+            let child_index = self
+                .resource_indices
+                .get(resource_start..resource_end)
+                .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?
                 .iter()
-                .map(|&i| &self.files[i as usize])
-                .take_while(|&item| !eq(item, file))
+                .map(|&i| self.files.get(i as usize))
+                .take_while(|&item| item.is_some_and(|item| !eq(item, file)))
                 .count();
             if parent.tag_name.is_empty() {
                 parent_name = self.get_tag_path(usize::try_from(file.parent_index)?, depth + 1)?;
@@ -185,19 +306,29 @@ impl ModuleFile {
     /// # Returns
     ///
     /// Returns a mutable reference to the file if the read operation is successful, or an [`Error`](`crate::Error`), a [`None`] if the file was not read (if tag offset is specified as invalid) or the containing the I/O error if any reading operation fails.
+    ///
+    /// # Errors
+    /// - If the index is out of bounds [`ModuleError::InvalidIndex`]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
     pub fn read_tag(&mut self, index: u32) -> Result<Option<&mut ModuleFileEntry>> {
-        let file = &mut self.files[index as usize];
+        let hd1_offset = self.hd1_data_offset();
+        let file = self
+            .files
+            .get_mut(index as usize)
+            .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
         if file.data_offset_flags.contains(DataOffsetType::DEBUG) {
             return Ok(None); // Currently not reading debug modules because we don't have an
                              // example.
         }
         if file.data_offset_flags.contains(DataOffsetType::USE_HD1) {
-            if let Some(ref mut module_file) = self.hd1_file {
-                let mut offset = self.file_data_offset - self.header.hd1_delta;
-                if self.header.version <= ModuleVersion::Season3 {
-                    offset = self.header.hd1_delta;
-                }
-                file.read_tag(module_file, offset, &self.blocks, &self.header.version)?;
+            if let Some(hd1_file) = &self.hd1_file {
+                file.read_tag(
+                    &mut *lock_hd1(hd1_file),
+                    hd1_offset,
+                    &self.blocks,
+                    &self.header.version,
+                    self.config.defer_resources,
+                )?;
             } else {
                 return Ok(None);
             }
@@ -207,29 +338,97 @@ impl ModuleFile {
                 self.file_data_offset,
                 &self.blocks,
                 &self.header.version,
+                self.config.defer_resources,
             )?;
         }
         Ok(Some(file))
     }
 
+    /// Reads a specific tag from the module file and returns its parsed [`TagFile`].
+    ///
+    /// This is a shorthand for [`read_tag`](`ModuleFile::read_tag`) followed by accessing
+    /// [`tag_info`](`ModuleFileEntry::tag_info`), for callers that only need the tag structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to read the tag from.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed tag file, or [`None`] if the file was not read or is not a tag (such as resources and raw files).
+    ///
+    /// # Errors
+    /// - If the tag fails to be read [`read_tag`](`ModuleFile::read_tag`)
+    pub fn read_tag_info(&mut self, index: u32) -> Result<Option<&TagFile>> {
+        Ok(self
+            .read_tag(index)?
+            .and_then(|file| file.tag_info.as_ref()))
+    }
+
+    /// Loads the resource sections of a tag that were skipped when it was read.
+    ///
+    /// This only has an effect if the module was opened with
+    /// [`defer_resources`](`ModuleFileBuilder::defer_resources`) and the tag has been read with
+    /// [`read_tag`](`ModuleFile::read_tag`). The position of its [`data_stream`](`ModuleFileEntry::data_stream`) is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to load the resources of.
+    ///
+    /// # Errors
+    /// - If the index is out of bounds [`ModuleError::InvalidIndex`]
+    /// - If the file is stored in an HD1 file that is not available [`ModuleError::DataNotAvailable`]
+    /// - If the reader fails to read or decompress the data [`ReadError`](`crate::Error::ReadError`)
+    pub fn load_resources(&mut self, index: u32) -> Result<()> {
+        let hd1_offset = self.hd1_data_offset();
+        let file = self
+            .files
+            .get_mut(index as usize)
+            .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
+        if !file.resources_deferred() {
+            return Ok(());
+        }
+        if file.data_offset_flags.contains(DataOffsetType::USE_HD1) {
+            let Some(hd1_file) = &self.hd1_file else {
+                return Err(Error::ModuleError(ModuleError::DataNotAvailable));
+            };
+            file.load_deferred_resources(&mut *lock_hd1(hd1_file), hd1_offset, &self.blocks)
+        } else if let Some(ref mut module_file) = self.module_file {
+            file.load_deferred_resources(module_file, self.file_data_offset, &self.blocks)
+        } else {
+            Err(Error::ModuleError(ModuleError::DataNotAvailable))
+        }
+    }
+
+    /// Finds the index of a tag in [`files`](`ModuleFile::files`) by its global ID.
+    ///
+    /// Only the file table is searched, so this does not read or decompress any tag data.
+    #[must_use]
+    pub fn find_tag(&self, tag_id: TagId) -> Option<FileIndex> {
+        self.files
+            .iter()
+            .position(|file| file.tag_id == tag_id.0)
+            .map(FileIndex)
+    }
+
     /// Searches for the index of the tag given the `global_id`.
     ///
     /// This function searches for the index of a tag in the [`files`](`ModuleFile::files`) vector using the provided
-    /// `global_id`. If the tag is found, it reads the tag using the [`read_tag`](`ModuleFile::read_tag`) function and
+    /// `tag_id`. If the tag is found, it reads the tag using the [`read_tag`](`ModuleFile::read_tag`) function and
     /// stores it in the index.
     ///
     /// # Arguments
     ///
-    /// * `global_id` - The global tag ID of the file to find. This ID is used to identify the
-    ///                 specific tag within the module file.
+    /// * `tag_id` - The global tag ID of the file to find. This ID is used to identify the
+    ///              specific tag within the module file.
     ///
     /// # Returns
     ///
     /// Returns a mutable reference to the file if successful. If the tag is not
     /// found or couldn't be read, it returns [`None`]. Any I/O error encountered during the operation is also returned
     /// if it occurs.
-    pub fn read_tag_from_id(&mut self, global_id: i32) -> Result<Option<&mut ModuleFileEntry>> {
-        if let Some(index) = self.files.iter().position(|file| file.tag_id == global_id) {
+    pub fn read_tag_from_id(&mut self, tag_id: TagId) -> Result<Option<&mut ModuleFileEntry>> {
+        if let Some(FileIndex(index)) = self.find_tag(tag_id) {
             let has_read = self.read_tag(u32::try_from(index)?)?;
             if let Some(tag) = has_read {
                 Ok(Some(tag))
@@ -240,4 +439,919 @@ impl ModuleFile {
             Ok(None)
         }
     }
+
+    /// Loads all dependencies of a tag that are present in the same module.
+    ///
+    /// This reads the tag at `index` (if not already loaded), and then reads every tag listed in its
+    /// [`dependencies`](`crate::TagFile::dependencies`) that can be found in this module. This can be used to warm
+    /// up the module before resolving references, for instance the shaders and bitmaps of a material.
+    ///
+    /// Dependencies residing in other modules are skipped, and can be retrieved using
+    /// [`external_dependencies`](`ModuleFile::external_dependencies`).
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to load the dependencies of.
+    ///
+    /// # Returns
+    ///
+    /// Returns the indices of the dependencies that were loaded. If the tag could not be read or is a raw file, an empty vector is returned.
+    ///
+    /// # Errors
+    /// - If any of the tags fail to be read [`read_tag`](`ModuleFile::read_tag`)
+    pub fn prefetch_dependencies(&mut self, index: u32) -> Result<Vec<usize>> {
+        let Some(tag) = self.read_tag(index)? else {
+            return Ok(Vec::new());
+        };
+        let dependency_ids: Vec<i32> = tag
+            .tag_info
+            .as_ref()
+            .map(|info| info.dependencies().iter().map(|dep| dep.tag_id).collect())
+            .unwrap_or_default();
+
+        let mut loaded = Vec::with_capacity(dependency_ids.len());
+        for tag_id in dependency_ids {
+            if tag_id == -1 {
+                continue;
+            }
+            if let Some(dep_index) = self.files.iter().position(|file| file.tag_id == tag_id) {
+                if self.read_tag(u32::try_from(dep_index)?)?.is_some() {
+                    loaded.push(dep_index);
+                }
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Gets the global tag ids of the dependencies of a tag that are not present in this module.
+    ///
+    /// The tag at `index` must already be loaded using [`read_tag`](`ModuleFile::read_tag`).
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to get the external dependencies of.
+    ///
+    /// # Errors
+    /// - If the index is out of bounds [`ModuleError::InvalidIndex`]
+    /// - If the tag info is not present [`TagError::NoTagInfo`]
+    pub fn external_dependencies(&self, index: u32) -> Result<Vec<i32>> {
+        let tag_info = self
+            .files
+            .get(index as usize)
+            .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?
+            .tag_info
+            .as_ref()
+            .ok_or(Error::TagError(TagError::NoTagInfo))?;
+        Ok(tag_info
+            .dependencies()
+            .iter()
+            .filter(|dep| dep.tag_id != -1)
+            .filter(|dep| !self.files.iter().any(|file| file.tag_id == dep.tag_id))
+            .map(|dep| dep.tag_id)
+            .collect())
+    }
+
+    /// Returns the indices of all file entries, sorted by tag group.
+    ///
+    /// The sort is stable, meaning entries of the same tag group keep their on-disk order.
+    /// [`files`](`ModuleFile::files`) itself is not modified.
+    #[must_use]
+    pub fn sorted_indices_by_group(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.files.len()).collect();
+        indices.sort_by(|&a, &b| self.files[a].tag_group.cmp(&self.files[b].tag_group));
+        indices
+    }
+
+    /// Returns the indices of all file entries, sorted by global tag id.
+    ///
+    /// The sort is stable, meaning entries sharing the same id (such as resources, which all have an id of -1)
+    /// keep their on-disk order. [`files`](`ModuleFile::files`) itself is not modified.
+    #[must_use]
+    pub fn sorted_indices_by_id(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.files.len()).collect();
+        indices.sort_by_key(|&index| self.files[index].tag_id);
+        indices
+    }
+
+    /// Resolves a [`FieldTagResource`] to the index of the file entry containing the resource.
+    ///
+    /// Resources owned by a tag are stored as separate file entries in the module. The
+    /// [`resource_index`](`FieldTagResource::resource_index`) of a tag resource field is relative to the resources of
+    /// its owning tag, which are listed in [`resource_indices`](`ModuleFile::resource_indices`) starting from
+    /// [`ModuleFileEntry::resource_index`].
+    ///
+    /// # Arguments
+    ///
+    /// * `parent_index` - The index of the file entry that owns the tag resource field.
+    /// * `resource` - The tag resource field read from the parent tag.
+    ///
+    /// # Returns
+    ///
+    /// Returns the index of the resource file entry, or [`None`] if the parent or resource index is out of range.
+    #[must_use]
+    pub fn resolve_tag_resource(
+        &self,
+        parent_index: u32,
+        resource: &FieldTagResource,
+    ) -> Option<usize> {
+        let parent = self.files.get(parent_index as usize)?;
+        let resource_index = i32::try_from(resource.resource_index).ok()?;
+        if parent.resource_index < 0 || resource_index >= parent.resource_count {
+            return None;
+        }
+        let index = usize::try_from(parent.resource_index + resource_index).ok()?;
+        let file_index = *self.resource_indices.get(index)? as usize;
+        (file_index < self.files.len()).then_some(file_index)
+    }
+
+    /// Gets the index of the file entry that owns a resource.
+    ///
+    /// The parent of a resource is usually the tag it belongs to, but can also be another resource for resources split
+    /// into multiple blocks. In that case, this can be called again on the returned index to reach the tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to get the parent of.
+    ///
+    /// # Returns
+    ///
+    /// Returns the index of the parent in [`files`](`ModuleFile::files`), or [`None`] if the file has no parent
+    /// ([`parent_index`](`ModuleFileEntry::parent_index`) is `-1`) or either index is out of range.
+    #[must_use]
+    pub fn parent_of(&self, index: u32) -> Option<usize> {
+        let parent_index = usize::try_from(self.files.get(index as usize)?.parent_index).ok()?;
+        (parent_index < self.files.len()).then_some(parent_index)
+    }
+
+    /// Checks whether a file entry is a resource, using the split between tags and resources stored in the module header.
+    ///
+    /// Resources are stored after all tags, starting at the resource index of the header (the file count minus the
+    /// resource count). If the header does not contain a valid index, the file is a resource if it has no
+    /// [`tag_id`](`ModuleFileEntry::tag_id`). A warning is logged if both checks disagree.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to check.
+    ///
+    /// # Returns
+    ///
+    /// Returns whether the file is a resource, or `false` if the index is out of range.
+    #[must_use]
+    pub fn is_resource_entry(&self, index: u32) -> bool {
+        let Some(file) = self.files.get(index as usize) else {
+            return false;
+        };
+        let is_resource = match u32::try_from(self.header.resource_index) {
+            Ok(resource_index) if resource_index <= self.header.file_count => {
+                index >= resource_index
+            }
+            _ => file.tag_id == -1,
+        };
+        if is_resource != (file.tag_id == -1) {
+            logging::warn!(
+                index,
+                tag_id = file.tag_id,
+                "resource index of the module header does not match the tag id of the file"
+            );
+        }
+        is_resource
+    }
+
+    /// Reads a specific tag from the module file without modifying the module.
+    ///
+    /// Unlike [`read_tag`](`ModuleFile::read_tag`), this function only requires a shared reference to the module,
+    /// as it opens a new handle to the module (or HD1) file for each call (or reads from the buffer of modules read using
+    /// [`read_from_slice`](`ModuleFile::read_from_slice`), or locks the HD1 handle passed to
+    /// [`ModuleFileBuilder::shared_hd1`]) and returns the tag data instead of storing it in the file entry. This allows a [`ModuleFile`] to be shared across threads, for instance using [`Arc`](`std::sync::Arc`).
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to read the tag from.
+    ///
+    /// # Returns
+    ///
+    /// Returns the loaded tag if successful, or [`None`] if the tag could not be read (the tag offset is invalid, or the
+    /// tag is stored in HD1 which is not available).
+    ///
+    /// # Errors
+    /// - If the index is out of bounds [`ModuleError::InvalidIndex`]
+    /// - If the module file fails to be opened or read [`ReadError`](`crate::Error::ReadError`)
+    /// - If any issues arise while reading non-raw tags: [`TagError`](`crate::common::errors::TagError`)
+    pub fn read_tag_owned(&self, index: u32) -> Result<Option<LoadedTag>> {
+        let file = self
+            .files
+            .get(index as usize)
+            .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
+        if file.data_offset_flags.contains(DataOffsetType::DEBUG) {
+            return Ok(None);
+        }
+        let data = if file.data_offset_flags.contains(DataOffsetType::USE_HD1) {
+            match (&self.hd1_path, &self.hd1_file) {
+                (Some(path), _) => {
+                    let mut reader = BufReader::new(File::open(path)?);
+                    file.read_data(&mut reader, self.hd1_data_offset(), &self.blocks)?
+                }
+                // Shared HD1 handles have no known path, so the handle itself is locked instead.
+                (None, Some(hd1_file)) => file.read_data(
+                    &mut *lock_hd1(hd1_file),
+                    self.hd1_data_offset(),
+                    &self.blocks,
+                )?,
+                (None, None) => return Ok(None),
+            }
+        } else {
+            match (&self.module_file, &self.file_path) {
+                (Some(ModuleSource::Memory(buffer)), _) => {
+                    let mut reader = Cursor::new(buffer.get_ref().as_slice());
+                    file.read_data(&mut reader, self.file_data_offset, &self.blocks)?
+                }
+                (_, Some(path)) => {
+                    let mut reader = BufReader::new(File::open(path)?);
+                    file.read_data(&mut reader, self.file_data_offset, &self.blocks)?
+                }
+                _ => return Ok(None),
+            }
+        };
+        let mut data_stream = BufReader::new(Cursor::new(data));
+        let tag_info = file.read_tag_info(&mut data_stream, &self.header.version)?;
+        Ok(Some(LoadedTag {
+            data_stream,
+            tag_info,
+        }))
+    }
+
+    /// Calculates the offset where file data starts inside the HD1 file.
+    ///
+    /// Every known version (up to and including [`ModuleVersion::Season3`]) stores this offset directly as
+    /// [`hd1_delta`](`ModuleHeader::hd1_delta`).
+    #[must_use]
+    pub fn hd1_data_offset(&self) -> u64 {
+        self.header.hd1_delta
+    }
+
+    /// Checks whether the module stores part of its data in an HD1 file.
+    ///
+    /// This only looks at the module header, and does not check whether the HD1 file exists. If it is required but
+    /// was not found (or was disabled through [`ModuleFileBuilder::use_hd1`]),
+    /// [`use_hd1`](`ModuleFile::use_hd1`) is `false` and reading files stored in it returns [`None`].
+    #[must_use]
+    pub fn requires_hd1(&self) -> bool {
+        self.header.hd1_delta != 0
+    }
+
+    /// Counts the files whose data is stored in the HD1 file.
+    #[must_use]
+    pub fn count_hd1_entries(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| file.data_offset_flags.contains(DataOffsetType::USE_HD1))
+            .count()
+    }
+
+    /// Reads a specific tag from the module file only if its tag group is in the given list.
+    ///
+    /// Tags of other groups are neither decompressed nor parsed, which avoids unnecessary work when
+    /// only some tag groups are of interest.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to read the tag from.
+    /// * `groups` - Tag groups to read, with or without trailing spaces (for instance `"mat"`).
+    ///
+    /// # Returns
+    ///
+    /// Returns the same as [`read_tag`](`ModuleFile::read_tag`), or [`None`] if the tag group does not match.
+    ///
+    /// # Errors
+    /// - If the index is out of bounds [`ModuleError::InvalidIndex`]
+    /// - If the tag fails to be read [`read_tag`](`ModuleFile::read_tag`)
+    pub fn read_tag_if_group(
+        &mut self,
+        index: u32,
+        groups: &[&str],
+    ) -> Result<Option<&mut ModuleFileEntry>> {
+        let file = self
+            .files
+            .get(index as usize)
+            .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
+        if !groups.iter().any(|group| file.is_group(group)) {
+            return Ok(None);
+        }
+        self.read_tag(index)
+    }
+
+    /// Loads a tag and reads its metadata into a new instance of `T` in a single call.
+    ///
+    /// This is the same as calling [`read_tag`](`ModuleFile::read_tag`) followed by
+    /// [`read_metadata`](`ModuleFileEntry::read_metadata`) on the returned entry, which avoids calling `read_metadata`
+    /// on a tag that has not been loaded yet. Unlike [`iter_metadata`](`ModuleFile::iter_metadata`), the
+    /// [`data_stream`](`ModuleFileEntry::data_stream`) of the tag is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to read the tag from.
+    ///
+    /// # Returns
+    ///
+    /// Returns the metadata of the tag, or [`None`] if the tag could not be loaded (see [`read_tag`](`ModuleFile::read_tag`)).
+    ///
+    /// # Errors
+    /// - If the tag fails to be read [`read_tag`](`ModuleFile::read_tag`)
+    /// - If the metadata fails to be read [`read_metadata`](`ModuleFileEntry::read_metadata`)
+    pub fn read_full<T: Default + TagStructure>(&mut self, index: u32) -> Result<Option<T>> {
+        let Some(tag) = self.read_tag(index)? else {
+            return Ok(None);
+        };
+        let mut metadata = T::default();
+        tag.read_metadata(&mut metadata)?;
+        Ok(Some(metadata))
+    }
+
+    /// Reads the metadata of every tag of the given tag group into a new instance of `T`.
+    ///
+    /// Tags are loaded lazily as the iterator is advanced. After the metadata of a tag has been read, its
+    /// [`data_stream`](`ModuleFileEntry::data_stream`) is dropped to keep memory usage low, as loading every tag in
+    /// a module can otherwise take up a large amount of memory, and the tag is marked as not loaded so that it can be read
+    /// again. Tags that could not be loaded (see [`read_tag`](`ModuleFile::read_tag`)) are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - Tag group to read, with or without trailing spaces (for instance `"mat"`).
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator over the index of each tag in [`files`](`ModuleFile::files`) and its metadata.
+    /// Errors are returned per tag, so that a single tag failing to be read does not stop the iteration.
+    pub fn iter_metadata<'a, T: Default + TagStructure + 'a>(
+        &'a mut self,
+        group: &str,
+    ) -> impl Iterator<Item = Result<(usize, T)>> + 'a {
+        let indices: Vec<usize> = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| file.is_group(group))
+            .map(|(index, _)| index)
+            .collect();
+        indices.into_iter().filter_map(move |index| {
+            let tag = match u32::try_from(index)
+                .map_err(Error::from)
+                .and_then(|index| self.read_tag(index))
+            {
+                Ok(Some(tag)) => tag,
+                Ok(None) => return None,
+                Err(error) => return Some(Err(error)),
+            };
+            let mut metadata = T::default();
+            let result = tag.read_metadata(&mut metadata);
+            tag.data_stream = None;
+            tag.is_loaded = false;
+            Some(result.map(|_| (index, metadata)))
+        })
+    }
+
+    /// Reads and decompresses the data of a file entry, regardless of its tag ID.
+    ///
+    /// Some modules only contain resources, where every [`tag_id`](`ModuleFileEntry::tag_id`) is `-1`. As these files do not
+    /// have a tag header, [`read_tag`](`ModuleFile::read_tag`) does not build [`tag_info`](`ModuleFileEntry::tag_info`) for them
+    /// and [`read_metadata`](`ModuleFileEntry::read_metadata`) cannot be used. This function should be used to get the raw bytes
+    /// of such resources instead. The data is returned directly and not stored in the file entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry to read.
+    ///
+    /// # Errors
+    /// - If the index is out of bounds [`ModuleError::InvalidIndex`]
+    /// - If the data is stored in an HD1 file that is not loaded or in a debug module [`ModuleError::DataNotAvailable`]
+    /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    /// - If the data fails to be decompressed [`DecompressionError`](`crate::Error::DecompressionError`)
+    pub fn read_resource_blob(&mut self, index: u32) -> Result<Vec<u8>> {
+        let hd1_offset = self.hd1_data_offset();
+        let file = self
+            .files
+            .get(index as usize)
+            .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
+        if file.data_offset_flags.contains(DataOffsetType::DEBUG) {
+            return Err(Error::ModuleError(ModuleError::DataNotAvailable));
+        }
+        if file.data_offset_flags.contains(DataOffsetType::USE_HD1) {
+            if let Some(hd1_file) = &self.hd1_file {
+                return file.read_data(&mut *lock_hd1(hd1_file), hd1_offset, &self.blocks);
+            }
+        } else if let Some(ref mut module_file) = self.module_file {
+            return file.read_data(module_file, self.file_data_offset, &self.blocks);
+        }
+        Err(Error::ModuleError(ModuleError::DataNotAvailable))
+    }
+
+    /// Extracts every resource owned by a tag to a directory.
+    ///
+    /// Each resource listed for the tag in [`resource_indices`](`ModuleFile::resource_indices`) is read using
+    /// [`read_resource_blob`](`ModuleFile::read_resource_blob`) and split into its sections. Every non-empty section is
+    /// written to `out_dir` as `{resource}_{section}.bin`, where `resource` is the index of the resource relative to the
+    /// tag (as used by [`FieldTagResource::resource_index`]) and `section` is one of `header`, `tag_data`,
+    /// `resource_data` or `actual_resource`. `out_dir` is created if it does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file entry owning the resources.
+    /// * `out_dir` - Directory to write the resources to.
+    ///
+    /// # Returns
+    ///
+    /// Returns the paths of the written files.
+    ///
+    /// # Errors
+    /// - If the index of the tag or of any of its resources is out of bounds [`ModuleError::InvalidIndex`]
+    /// - If any resource fails to be read [`read_resource_blob`](`ModuleFile::read_resource_blob`)
+    /// - If the section sizes of a resource do not match its data [`TagError::DataOutOfBounds`]
+    /// - If the directory or files fail to be written [`ReadError`](`crate::Error::ReadError`)
+    pub fn extract_resources(&mut self, index: u32, out_dir: &Path) -> Result<Vec<PathBuf>> {
+        let parent = self
+            .files
+            .get(index as usize)
+            .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
+        let (first_resource, resource_count) = (parent.resource_index, parent.resource_count);
+
+        create_dir_all(out_dir)?;
+        let mut paths = Vec::new();
+        for resource in 0..resource_count.max(0) {
+            let file_index = usize::try_from(first_resource + resource)
+                .ok()
+                .and_then(|index| self.resource_indices.get(index))
+                .copied()
+                .filter(|&file_index| (file_index as usize) < self.files.len())
+                .ok_or(Error::ModuleError(ModuleError::InvalidIndex))?;
+            let data = self.read_resource_blob(file_index)?;
+            let sections = self.files[file_index as usize].split_data(&data)?;
+            for (name, section) in [
+                ("header", sections.header),
+                ("tag_data", sections.tag_data),
+                ("resource_data", sections.resource_data),
+                ("actual_resource", sections.actual_resource),
+            ] {
+                if section.is_empty() {
+                    continue;
+                }
+                let path = out_dir.join(format!("{resource}_{name}.bin"));
+                write(&path, section)?;
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Gets the version of the game build that produced this module.
+    ///
+    /// The value is shared between every module shipped with the same build of the game, and changes with each
+    /// patch. It is not a readable version number, but it can be compared against the value read from a known build
+    /// to gate behavior on specific patches.
+    #[must_use]
+    pub fn build_version(&self) -> u64 {
+        self.header.build_version
+    }
+
+    /// Gets the unique identifier of this module.
+    #[must_use]
+    pub fn module_id(&self) -> i64 {
+        self.header.module_id
+    }
+
+    /// Summarizes the contents of the module, such as the number of tags in each group and the total size of its files.
+    ///
+    /// This only uses the headers read when the module was opened, and does not read any tags.
+    #[must_use]
+    pub fn inventory(&self) -> ModuleInventory {
+        ModuleInventory::new(self)
+    }
+
+    /// Compares the tags of this module against another module, such as the same module after a game update.
+    ///
+    /// Tags are matched by their global ID and compared using their [`asset_hash`](`ModuleFileEntry::asset_hash`). This only
+    /// uses the file tables read when the modules were opened, and does not read or decompress any tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The module to compare against, treated as the newer module.
+    #[must_use]
+    pub fn diff(&self, other: &ModuleFile) -> ModuleDiff {
+        ModuleDiff::new(self, other)
+    }
+
+    /// Gets the metadata of every file entry in the module, in the order of [`files`](`ModuleFile::files`).
+    ///
+    /// This only uses the file table read when the module was opened, and does not read any tags. It is meant for
+    /// comparing the layout of modules, for instance across versions.
+    #[must_use]
+    pub fn entry_table(&self) -> Vec<ModuleFileMeta> {
+        self.files.iter().map(ModuleFileMeta::from).collect()
+    }
+
+    /// Gets the table of blocks making up the files in this module.
+    ///
+    /// Files reference a contiguous range of this table through their
+    /// [`block_index`](`ModuleFileEntry::block_index`) and [`block_count`](`ModuleFileEntry::block_count`) getters.
+    #[must_use]
+    pub fn blocks(&self) -> &[ModuleBlockEntry] {
+        &self.blocks
+    }
+
+    /// Reads multiple tags given their `global_id`s.
+    ///
+    /// Unlike calling [`read_tag_from_id`](`ModuleFile::read_tag_from_id`) for each ID, which searches all files every time,
+    /// this builds a map of tag IDs once and resolves all IDs in a single pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `global_ids` - The global tag IDs of the files to read.
+    ///
+    /// # Returns
+    ///
+    /// Returns the index of each tag in [`files`](`ModuleFile::files`), in the same order as `global_ids`.
+    /// An entry is [`None`] if the tag is not in the module or could not be read (see [`read_tag`](`ModuleFile::read_tag`)).
+    ///
+    /// # Errors
+    /// - If any tag fails to be read [`read_tag`](`ModuleFile::read_tag`)
+    pub fn read_tags_from_ids(&mut self, global_ids: &[i32]) -> Result<Vec<Option<usize>>> {
+        let mut indices = HashMap::with_capacity(self.files.len());
+        for (index, file) in self.files.iter().enumerate() {
+            indices.entry(file.tag_id).or_insert(index);
+        }
+        global_ids
+            .iter()
+            .map(|global_id| {
+                let Some(&index) = indices.get(global_id) else {
+                    return Ok(None);
+                };
+                Ok(self.read_tag(u32::try_from(index)?)?.map(|_| index))
+            })
+            .collect()
+    }
+
+    /// Reads every file in the module, continuing past files that fail to be read.
+    ///
+    /// Unlike calling [`read_tag`](`ModuleFile::read_tag`) for each file and stopping at the first error, this attempts
+    /// every file and collects the results, so that a few corrupt tags do not prevent reading the rest of the module.
+    /// Files that are not read by [`read_tag`](`ModuleFile::read_tag`) (such as files in debug modules) are reported as
+    /// successful.
+    ///
+    /// # Returns
+    ///
+    /// Returns the index in [`files`](`ModuleFile::files`) and the result of reading each file, in order.
+    pub fn read_all_tags_lenient(&mut self) -> Vec<(usize, Result<()>)> {
+        (0..self.files.len())
+            .map(|index| {
+                let result = u32::try_from(index)
+                    .map_err(Error::from)
+                    .and_then(|index| self.read_tag(index).map(|_| ()));
+                (index, result)
+            })
+            .collect()
+    }
+
+    /// Checks the [`AnyTag`](`crate::tag::types::common_types::AnyTag`) of every tag in the module against its file entry.
+    ///
+    /// Every tag is read (see [`read_tag`](`ModuleFile::read_tag`)) and checked with
+    /// [`verify_any_tag`](`ModuleFileEntry::verify_any_tag`). Resources, raw files and tags that cannot be read are skipped.
+    /// Mismatches across many tags usually point to a systematic offset error rather than a few corrupt tags.
+    ///
+    /// # Returns
+    ///
+    /// Returns the indices in [`files`](`ModuleFile::files`) of the tags whose IDs do not match.
+    ///
+    /// # Errors
+    /// - If any tag fails to be read [`read_tag`](`ModuleFile::read_tag`)
+    /// - If the `AnyTag` of any tag fails to be read [`read_any_tag`](`ModuleFileEntry::read_any_tag`)
+    pub fn verify_any_tags(&mut self) -> Result<Vec<usize>> {
+        let mut mismatched = Vec::new();
+        for index in 0..self.files.len() {
+            if self.files[index].tag_id == -1 {
+                continue;
+            }
+            let Some(file) = self.read_tag(u32::try_from(index)?)? else {
+                continue;
+            };
+            if file.tag_info.is_some() && !file.verify_any_tag()? {
+                mismatched.push(index);
+            }
+        }
+        Ok(mismatched)
+    }
+}
+
+/// Locks an HD1 file for reading.
+///
+/// Readers always seek before reading, so a handle poisoned by a panic in another module is still usable.
+fn lock_hd1(hd1_file: &SharedHd1) -> MutexGuard<'_, BufReader<File>> {
+    hd1_file.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[derive(Debug)]
+/// Source that the data of a module is read from.
+enum ModuleSource {
+    /// Module stored on disk.
+    File(BufReader<File>),
+    /// Module stored in memory.
+    Memory(Cursor<Vec<u8>>),
+}
+
+impl Read for ModuleSource {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        match self {
+            Self::File(reader) => reader.read(buf),
+            Self::Memory(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl BufRead for ModuleSource {
+    fn fill_buf(&mut self) -> IoResult<&[u8]> {
+        match self {
+            Self::File(reader) => reader.fill_buf(),
+            Self::Memory(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self {
+            Self::File(reader) => reader.consume(amt),
+            Self::Memory(reader) => reader.consume(amt),
+        }
+    }
+}
+
+impl Seek for ModuleSource {
+    fn seek(&mut self, pos: SeekFrom) -> IoResult<u64> {
+        match self {
+            Self::File(reader) => reader.seek(pos),
+            Self::Memory(reader) => reader.seek(pos),
+        }
+    }
+}
+
+impl BufReaderExt for ModuleSource {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::file::tests::entry;
+    use std::{env, fs, process};
+
+    #[test]
+    /// Verifies that sorting by group or id is stable and does not reorder `files`.
+    fn test_sorted_indices_stable() {
+        let module = ModuleFile {
+            files: vec![
+                entry("mat ", 5),
+                entry("bitm", -1),
+                entry("mat ", 2),
+                entry("bitm", -1),
+                entry("bitm", 1),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(module.sorted_indices_by_group(), vec![1, 3, 4, 0, 2]);
+        assert_eq!(module.sorted_indices_by_id(), vec![1, 3, 4, 2, 0]);
+        assert_eq!(module.files[0].tag_id, 5);
+    }
+
+    #[test]
+    /// Verifies that tag resources are mapped to file indices through `resource_indices`.
+    fn test_resolve_tag_resource() {
+        let mut parent = entry("bitm", 1);
+        parent.resource_index = 1;
+        parent.resource_count = 2;
+        let module = ModuleFile {
+            files: vec![parent, entry("", -1), entry("", -1), entry("", -1)],
+            resource_indices: vec![1, 3, 2],
+            ..Default::default()
+        };
+
+        let mut resource = FieldTagResource::default();
+        resource.resource_index = 1;
+        assert_eq!(module.resolve_tag_resource(0, &resource), Some(2));
+        resource.resource_index = 0;
+        assert_eq!(module.resolve_tag_resource(0, &resource), Some(3));
+        resource.resource_index = 2;
+        assert_eq!(module.resolve_tag_resource(0, &resource), None);
+        assert_eq!(module.resolve_tag_resource(4, &resource), None);
+    }
+
+    #[test]
+    /// Verifies the HD1 data offset for each module version.
+    fn test_hd1_data_offset() {
+        for version in [
+            ModuleVersion::Flight1,
+            ModuleVersion::Release,
+            ModuleVersion::CampaignFlight,
+            ModuleVersion::Season3,
+        ] {
+            let mut module = ModuleFile {
+                file_data_offset: 0x10000,
+                ..Default::default()
+            };
+            module.header.version = version;
+            module.header.hd1_delta = 0x4000;
+            assert_eq!(module.hd1_data_offset(), 0x4000);
+        }
+    }
+
+    #[test]
+    /// Verifies that HD1 requirements are detected from metadata, and that HD1 files are skipped when it is missing.
+    fn test_requires_hd1() {
+        let mut hd1_entry = entry("bitm", 1);
+        hd1_entry.data_offset_flags = DataOffsetType::USE_HD1;
+        let mut module = ModuleFile {
+            files: vec![entry("mat ", 0), hd1_entry, entry("bitm", 2)],
+            ..Default::default()
+        };
+        assert!(!module.requires_hd1());
+        assert_eq!(module.count_hd1_entries(), 1);
+
+        module.header.hd1_delta = 0x4000;
+        assert!(module.requires_hd1());
+        assert!(!module.use_hd1);
+        assert!(module.read_tag(1).unwrap().is_none());
+    }
+
+    #[test]
+    /// Verifies that the resources of a tag are written section by section, skipping empty sections.
+    fn test_extract_resources() {
+        let mut parent = entry("mode", 1);
+        parent.resource_index = 0;
+        parent.resource_count = 1;
+        let mut resource = entry("", -1);
+        resource.total_compressed_size = 6;
+        resource.total_uncompressed_size = 6;
+        resource.uncompressed_resource_data_size = 2;
+        resource.uncompressed_actual_resource_size = 4;
+        let mut module = ModuleFile {
+            files: vec![parent, resource],
+            resource_indices: vec![1],
+            module_file: Some(ModuleSource::Memory(Cursor::new(vec![1, 2, 3, 4, 5, 6]))),
+            ..Default::default()
+        };
+
+        let out_dir = env::temp_dir().join(format!("infinite-rs-resources-{}", process::id()));
+        let paths = module.extract_resources(0, &out_dir).unwrap();
+        assert_eq!(
+            paths,
+            [
+                out_dir.join("0_resource_data.bin"),
+                out_dir.join("0_actual_resource.bin")
+            ]
+        );
+        assert_eq!(fs::read(&paths[0]).unwrap(), [1, 2]);
+        assert_eq!(fs::read(&paths[1]).unwrap(), [3, 4, 5, 6]);
+        fs::remove_dir_all(&out_dir).unwrap();
+
+        module.resource_indices = vec![5];
+        assert!(matches!(
+            module.extract_resources(0, &out_dir),
+            Err(Error::ModuleError(ModuleError::InvalidIndex))
+        ));
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[test]
+    /// Verifies that the parent of a resource is returned, and that missing or invalid parents return nothing.
+    fn test_parent_of() {
+        let mut resource = entry("", -1);
+        resource.parent_index = 0;
+        let mut invalid = entry("", -1);
+        invalid.parent_index = 7;
+        let mut tag = entry("mode", 1);
+        tag.parent_index = -1;
+        let module = ModuleFile {
+            files: vec![tag, resource, invalid],
+            ..Default::default()
+        };
+
+        assert_eq!(module.parent_of(1), Some(0));
+        assert_eq!(module.parent_of(0), None);
+        assert_eq!(module.parent_of(2), None);
+        assert_eq!(module.parent_of(3), None);
+    }
+
+    #[test]
+    /// Verifies that resources are classified using the resource index of the header, falling back to the tag ID.
+    fn test_is_resource_entry() {
+        let mut module = ModuleFile {
+            files: vec![entry("mat ", 1), entry("bitm", 2), entry("", -1)],
+            ..Default::default()
+        };
+        module.header.file_count = 3;
+        module.header.resource_index = 2;
+        assert!(!module.is_resource_entry(0));
+        assert!(!module.is_resource_entry(1));
+        assert!(module.is_resource_entry(2));
+        assert!(!module.is_resource_entry(3));
+
+        module.header.resource_index = 1;
+        assert!(module.is_resource_entry(1));
+
+        module.header.resource_index = -1;
+        assert!(!module.is_resource_entry(1));
+        assert!(module.is_resource_entry(2));
+    }
+
+    #[test]
+    /// Verifies that resource blobs are read even though they do not have a tag ID.
+    fn test_read_resource_blob() {
+        let mut resource = entry("", -1);
+        resource.total_compressed_size = 4;
+        resource.total_uncompressed_size = 4;
+        let mut module = ModuleFile {
+            files: vec![resource],
+            module_file: Some(ModuleSource::Memory(Cursor::new(vec![
+                0xAA, 0xBB, 0x01, 0x02, 0x03, 0x04,
+            ]))),
+            file_data_offset: 2,
+            ..Default::default()
+        };
+
+        assert_eq!(module.read_resource_blob(0).unwrap(), vec![1, 2, 3, 4]);
+        assert!(module.files[0].data_stream.is_none());
+
+        module.files[0].data_offset_flags = DataOffsetType::USE_HD1;
+        assert!(matches!(
+            module.read_resource_blob(0),
+            Err(Error::ModuleError(ModuleError::DataNotAvailable))
+        ));
+        assert!(matches!(
+            module.read_resource_blob(1),
+            Err(Error::ModuleError(ModuleError::InvalidIndex))
+        ));
+    }
+
+    #[test]
+    /// Verifies that corrupted parent and resource indices return an error instead of panicking.
+    fn test_tag_path_invalid_index() {
+        let mut child = entry("", -1);
+        child.parent_index = 5;
+        let mut module = ModuleFile {
+            files: vec![child],
+            ..Default::default()
+        };
+        assert!(matches!(
+            module.get_tag_path(0, 0),
+            Err(Error::ModuleError(ModuleError::InvalidIndex))
+        ));
+
+        let mut parent = entry("bitm", 1);
+        parent.resource_index = 1;
+        parent.resource_count = 4;
+        module.files.push(parent);
+        module.files[0].parent_index = 1;
+        module.resource_indices = vec![0];
+        assert!(matches!(
+            module.get_tag_path(0, 0),
+            Err(Error::ModuleError(ModuleError::InvalidIndex))
+        ));
+    }
+
+    #[test]
+    /// Verifies that the resource range of a tag maps to the file indices of its resources.
+    fn test_resource_range() {
+        let mut parent = entry("bitm", 1);
+        parent.resource_index = 1;
+        parent.resource_count = 2;
+        let module = ModuleFile {
+            files: vec![entry("", -1), parent, entry("", -1), entry("", -1)],
+            resource_indices: vec![0, 2, 3],
+            ..Default::default()
+        };
+
+        let range = module.files[1].resource_range();
+        assert_eq!(range, 1..3);
+        for &file_index in &module.resource_indices[range] {
+            let resource = &module.files[file_index as usize];
+            assert_eq!(resource.tag_id, -1);
+        }
+        assert!(module.files[0].resource_range().is_empty());
+
+        let mut invalid = entry("bitm", 2);
+        invalid.resource_index = -1;
+        invalid.resource_count = 2;
+        assert!(invalid.resource_range().is_empty());
+    }
+
+    #[test]
+    /// Verifies that tags are resolved by ID in input order, including missing IDs.
+    fn test_read_tags_from_ids() {
+        let mut debug = entry("mat ", 7);
+        debug.data_offset_flags = DataOffsetType::DEBUG;
+        let mut module = ModuleFile {
+            files: vec![entry("bitm", -1), debug, entry("mat ", 7)],
+            ..Default::default()
+        };
+
+        // Debug files are not read, so the first file with the ID is returned as not read.
+        assert_eq!(
+            module.read_tags_from_ids(&[3, 7, -1]).unwrap(),
+            vec![None, None, Some(0)]
+        );
+    }
 }
@@ -1,7 +1,11 @@
 //! Main Interface for reading module files.
 
 pub mod block;
+pub mod builder;
+pub mod diff;
 pub mod file;
 pub mod header;
+pub mod inventory;
 pub mod kraken;
 pub mod loader;
+pub mod set;
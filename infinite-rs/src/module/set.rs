@@ -0,0 +1,294 @@
+//! Collection of modules that can be searched together.
+
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "walkdir")]
+use std::io::Error as IoError;
+#[cfg(feature = "walkdir")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "walkdir")]
+use super::builder::ModuleFileBuilder;
+use super::{file::ModuleFileEntry, loader::ModuleFile};
+use crate::{FileIndex, Result, TagId};
+
+#[derive(Default, Debug)]
+/// Set of modules, indexed by module ID and by the IDs of the tags they contain.
+///
+/// Tags often reference tags stored in other modules. [`ModuleSet`] allows these references to be resolved
+/// without searching every module manually.
+pub struct ModuleSet {
+    /// Modules in the set, in the order they were added.
+    pub modules: Vec<ModuleFile>,
+    /// Map of [`module_id`](`crate::module::header::ModuleHeader::module_id`) to index in [`modules`](`ModuleSet::modules`).
+    module_ids: HashMap<i64, usize>,
+    /// Map of [`tag_id`](`ModuleFileEntry::tag_id`) to the index of the module and the index of the file inside it.
+    tag_ids: HashMap<TagId, (usize, FileIndex)>,
+}
+
+impl ModuleSet {
+    /// Creates a new empty set of modules.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every module in the given deploy folders into a new set.
+    ///
+    /// Folders are walked recursively, and files are loaded in the order of their names, so that the
+    /// resulting set is the same on every platform. This function requires the `walkdir` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - Deploy folders (or subfolders of it) to load modules from.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use infinite_rs::module::set::ModuleSet;
+    ///
+    /// let modules = ModuleSet::from_deploy(&["C:/XboxGames/Halo Infinite/Content/deploy/"]).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// - If a folder fails to be walked [`ReadError`](`crate::Error::ReadError`)
+    /// - If any module fails to be read [`ModuleFile::from_path`]
+    #[cfg(feature = "walkdir")]
+    pub fn from_deploy<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let mut set = Self::new();
+        for path in module_paths(paths) {
+            set.add(ModuleFile::from_path(path?)?);
+        }
+        Ok(set)
+    }
+
+    /// Finds the first module in the given deploy folders that contains a tag, without loading any tags.
+    ///
+    /// Modules are visited in the same order as in [`from_deploy`](`ModuleSet::from_deploy`), but only their header and
+    /// file table are read, and each module is closed before the next one is opened. This is much faster than
+    /// loading the whole deploy folder when only the location of a single tag is needed. This function requires the
+    /// `walkdir` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - Deploy folders (or subfolders of it) to search.
+    /// * `tag_id` - The global tag ID of the tag to find, see [`TagId::from_path`] to find a tag by its path.
+    ///
+    /// # Returns
+    ///
+    /// The path of the module and the index of the tag in its [`files`](`ModuleFile::files`), or [`None`] if no module
+    /// contains the tag.
+    ///
+    /// # Errors
+    /// - If a folder fails to be walked [`ReadError`](`crate::Error::ReadError`)
+    /// - If any module fails to be read [`ModuleFile::from_path`]
+    #[cfg(feature = "walkdir")]
+    pub fn locate_tag<P: AsRef<Path>>(
+        paths: &[P],
+        tag_id: TagId,
+    ) -> Result<Option<(PathBuf, FileIndex)>> {
+        for path in module_paths(paths) {
+            let path = path?;
+            let module = ModuleFileBuilder::new()
+                .use_hd1(false)
+                .resolve_tag_names(false)
+                .open(&path)?;
+            if let Some(file_index) = module.find_tag(tag_id) {
+                return Ok(Some((path, file_index)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Adds a module to the set and indexes its tags.
+    ///
+    /// If a tag is present in more than one module, the module added first is used when resolving it.
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - The module to add.
+    ///
+    /// # Returns
+    ///
+    /// Index of the module in [`modules`](`ModuleSet::modules`).
+    pub fn add(&mut self, module: ModuleFile) -> usize {
+        let module_index = self.modules.len();
+        self.module_ids
+            .entry(module.header.module_id)
+            .or_insert(module_index);
+        for (file_index, file) in module.files.iter().enumerate() {
+            if file.tag_id != -1 {
+                self.tag_ids
+                    .entry(TagId(file.tag_id))
+                    .or_insert((module_index, FileIndex(file_index)));
+            }
+        }
+        self.modules.push(module);
+        module_index
+    }
+
+    /// Gets a module by its [`module_id`](`crate::module::header::ModuleHeader::module_id`).
+    #[must_use]
+    pub fn module_by_id(&self, module_id: i64) -> Option<&ModuleFile> {
+        self.module_ids
+            .get(&module_id)
+            .map(|&index| &self.modules[index])
+    }
+
+    /// Finds the module containing a tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag_id` - The global tag ID of the tag to find.
+    ///
+    /// # Returns
+    ///
+    /// The index of the module in [`modules`](`ModuleSet::modules`) and the index of the tag in its
+    /// [`files`](`ModuleFile::files`), or [`None`] if no module contains the tag.
+    #[must_use]
+    pub fn find_tag(&self, tag_id: TagId) -> Option<(usize, FileIndex)> {
+        self.tag_ids.get(&tag_id).copied()
+    }
+
+    /// Reads a tag from whichever module in the set contains it.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag_id` - The global tag ID of the tag to read.
+    ///
+    /// # Returns
+    ///
+    /// Returns the same as [`read_tag`](`ModuleFile::read_tag`), or [`None`] if no module contains the tag.
+    ///
+    /// # Errors
+    /// - If the tag fails to be read [`read_tag`](`ModuleFile::read_tag`)
+    pub fn read_tag_from_id(&mut self, tag_id: TagId) -> Result<Option<&mut ModuleFileEntry>> {
+        let Some((module_index, file_index)) = self.find_tag(tag_id) else {
+            return Ok(None);
+        };
+        self.modules[module_index].read_tag(u32::try_from(file_index.0)?)
+    }
+
+    /// Reads a tag by its path from whichever module in the set contains it.
+    ///
+    /// The path is hashed into a [`TagId`] using [`TagId::from_path`], so it has to match the path the game hashed
+    /// exactly. This allows tags to be found by name in modules that do not store a string table.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the tag, including its extension.
+    ///
+    /// # Returns
+    ///
+    /// The index of the module in [`modules`](`ModuleSet::modules`) and the index of the tag in its
+    /// [`files`](`ModuleFile::files`), or [`None`] if no module contains the tag.
+    ///
+    /// # Errors
+    /// - If the tag fails to be read [`read_tag`](`ModuleFile::read_tag`)
+    pub fn read_tag_from_name(&mut self, path: &str) -> Result<Option<(usize, FileIndex)>> {
+        let Some((module_index, file_index)) = self.find_tag(TagId::from_path(path)) else {
+            return Ok(None);
+        };
+        self.modules[module_index].read_tag(u32::try_from(file_index.0)?)?;
+        Ok(Some((module_index, file_index)))
+    }
+}
+
+/// Groups the files of several modules by their [`asset_hash`](`ModuleFileEntry::asset_hash`), keeping only hashes
+/// shared by more than one file.
+///
+/// Files with the same asset hash were built from the same source asset, and are usually stored more than once across
+/// a deploy folder. Only the file tables are used, so no tag data is read. Files with an asset hash of zero are ignored.
+///
+/// # Arguments
+///
+/// * `modules` - Modules to search, for instance [`modules`](`ModuleSet::modules`).
+///
+/// # Returns
+///
+/// Returns a map, sorted by asset hash, of each duplicated asset hash to the index of the module in `modules` and the
+/// index of the file in its [`files`](`ModuleFile::files`), for every file with that hash.
+#[must_use]
+pub fn find_duplicate_assets(modules: &[ModuleFile]) -> BTreeMap<i128, Vec<(usize, FileIndex)>> {
+    let mut assets: BTreeMap<i128, Vec<(usize, FileIndex)>> = BTreeMap::new();
+    for (module_index, module) in modules.iter().enumerate() {
+        for (file_index, file) in module.files.iter().enumerate() {
+            if file.asset_hash != 0 {
+                assets
+                    .entry(file.asset_hash)
+                    .or_default()
+                    .push((module_index, FileIndex(file_index)));
+            }
+        }
+    }
+    assets.retain(|_, files| files.len() > 1);
+    assets
+}
+
+/// Walks the given deploy folders recursively, returning the paths of module files in the order of their names.
+#[cfg(feature = "walkdir")]
+fn module_paths<P: AsRef<Path>>(paths: &[P]) -> impl Iterator<Item = Result<PathBuf>> + '_ {
+    paths
+        .iter()
+        .flat_map(|path| walkdir::WalkDir::new(path).sort_by_file_name())
+        .filter_map(|entry| match entry {
+            Ok(entry) => (entry.file_type().is_file()
+                && entry.path().extension().is_some_and(|ext| ext == "module"))
+            .then(|| Ok(entry.into_path())),
+            Err(error) => Some(Err(IoError::from(error).into())),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::file::tests::entry;
+
+    fn module(module_id: i64, tag_ids: &[i32]) -> ModuleFile {
+        let mut module = ModuleFile::default();
+        module.header.module_id = module_id;
+        module.files = tag_ids.iter().map(|&tag_id| entry("", tag_id)).collect();
+        module
+    }
+
+    #[test]
+    /// Verifies that modules and tags are indexed, with the first module winning for duplicate tags.
+    fn test_add_indexes_tags() {
+        let mut set = ModuleSet::new();
+        assert_eq!(set.add(module(10, &[1, -1, 2])), 0);
+        assert_eq!(set.add(module(20, &[-1, 2, 3])), 1);
+
+        assert_eq!(set.module_by_id(20).unwrap().header.module_id, 20);
+        assert!(set.module_by_id(30).is_none());
+        assert_eq!(set.find_tag(TagId(2)), Some((0, FileIndex(2))));
+        assert_eq!(set.find_tag(TagId(3)), Some((1, FileIndex(2))));
+        assert_eq!(set.find_tag(TagId::NONE), None);
+    }
+
+    #[test]
+    /// Verifies that tags are found by the hash of their path, and that unknown paths are not.
+    fn test_read_tag_from_name() {
+        let path = "objects/characters/spartan_armor/spartan_armor.model";
+        let mut set = ModuleSet::new();
+        set.add(module(10, &[TagId::from_path(path).0]));
+
+        assert_eq!(
+            set.find_tag(TagId::from_path(path)),
+            Some((0, FileIndex(0)))
+        );
+        assert_eq!(set.read_tag_from_name("missing.model").unwrap(), None);
+    }
+
+    #[test]
+    /// Verifies that files sharing an asset hash are grouped across modules, ignoring unique and unset hashes.
+    fn test_find_duplicate_assets() {
+        let mut modules = vec![module(10, &[1, 2, 3]), module(20, &[4, 5])];
+        modules[0].files[0].asset_hash = 0x10;
+        modules[0].files[1].asset_hash = 0x20;
+        modules[1].files[0].asset_hash = 0x30;
+        modules[1].files[1].asset_hash = 0x10;
+
+        let duplicates = find_duplicate_assets(&modules);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[&0x10], [(0, FileIndex(0)), (1, FileIndex(1))]);
+    }
+}
@@ -0,0 +1,34 @@
+//! Commonly used types, re-exported for convenience.
+//!
+//! Field types are otherwise nested deep inside [`crate::tag::types::common_types`]. Glob importing this module brings
+//! them into scope alongside the types needed to load modules and read tags. The full paths remain available.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use infinite_rs::prelude::*;
+//!
+//! fn count_references(path: &str, index: u32) -> Result<usize> {
+//!     let mut module = ModuleFile::from_path(path)?;
+//!     let tag = module.read_tag_info(index)?;
+//!     Ok(tag.map_or(0, |tag| tag.dependency_refs().len()))
+//! }
+//!
+//! let reference = FieldReference::default();
+//! let flags = FieldBool(true);
+//! assert!(flags.0);
+//! assert!(!reference.is_group("mat"));
+//! ```
+
+#[doc(no_inline)]
+pub use crate::common::errors::{Error, Result};
+#[doc(no_inline)]
+pub use crate::common::ids::{FileIndex, TagId};
+#[doc(no_inline)]
+pub use crate::module::file::{ModuleFileEntry, TagStructure};
+#[doc(no_inline)]
+pub use crate::module::loader::ModuleFile;
+#[doc(no_inline)]
+pub use crate::tag::loader::TagFile;
+#[doc(no_inline)]
+pub use crate::tag::types::common_types::*;
@@ -13,7 +13,7 @@ pub struct TagDataReference {
     pub parent_struct_index: i32,
     /// Unknown: seems to vary (maybe enum?).
     unknown: i32,
-    /// The index of the tag struct containing the referenced data.
+    /// The index of the data block containing the referenced data.
     /// Can be -1 for null references.
     pub target_index: i32,
     /// The index of the data block containing the tag field.
@@ -32,7 +32,8 @@ pub struct TagDataBlock {
     padding: u16,
     /// Where the data block is stored.
     pub section_type: TagSectionType,
-    /// Offset of where the data is stored from the start of the tag file.
+    /// Offset of where the data is stored from the start of its section (see [`section_type`](`TagDataBlock::section_type`)).
+    /// Use [`TagFile::absolute_offset`](`crate::tag::loader::TagFile::absolute_offset`) to get the offset from the start of the tag file.
     pub offset: u64,
 }
 
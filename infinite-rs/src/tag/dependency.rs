@@ -3,6 +3,7 @@
 use byteorder::{ReadBytesExt, LE};
 
 use crate::common::extensions::{BufReaderExt, Enumerable};
+use crate::tag::group::{fourcc_matches, FourCc};
 use crate::Result;
 
 #[derive(Default, Debug)]
@@ -31,7 +32,7 @@ pub struct TagDependency {
 
 impl Enumerable for TagDependency {
     fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
-        self.tag_group = reader.read_fixed_string(4)?.chars().rev().collect(); // Reverse string
+        self.tag_group = FourCc::read(reader)?.to_string();
         self.name_offset = reader.read_u32::<LE>()?;
         self.asset_id = reader.read_u64::<LE>()?;
         self.tag_id = reader.read_i32::<LE>()?;
@@ -39,3 +40,15 @@ impl Enumerable for TagDependency {
         Ok(())
     }
 }
+
+impl TagDependency {
+    /// Checks whether the dependency belongs to a group, ignoring trailing spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The 4 character code of the group, with or without trailing spaces.
+    #[must_use]
+    pub fn is_group(&self, group: &str) -> bool {
+        fourcc_matches(&self.tag_group, group)
+    }
+}
@@ -0,0 +1,235 @@
+//! Known tag groups, identified by the 4 character code stored in each file entry.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Read;
+use std::str::from_utf8;
+
+use crate::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// 4 character code identifying a tag group, such as `"mat "` or `"bitm"`.
+///
+/// Codes are stored as little-endian integers, so the bytes in the file are in reverse order (`" tam"`).
+/// The bytes are reversed before being decoded, which keeps multi-byte characters intact.
+/// A code made of `0xFF` bytes is used where no group is set, and is displayed as an empty string.
+pub struct FourCc([u8; 4]);
+
+impl FourCc {
+    /// Code used where no group is set.
+    pub const NONE: Self = Self([0xFF; 4]);
+
+    /// Reads a 4 character code from the reader.
+    ///
+    /// # Errors
+    /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    /// - If the bytes are not valid UTF-8 [`Utf8ReadingError`](`crate::Error::Utf8ReadingError`)
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut bytes = [0; 4];
+        reader.read_exact(&mut bytes)?;
+        bytes.reverse();
+        let fourcc = Self(bytes);
+        if fourcc != Self::NONE {
+            String::from_utf8(bytes.to_vec())?;
+        }
+        Ok(fourcc)
+    }
+
+    /// Gets the bytes of the code, in display order.
+    #[must_use]
+    pub fn bytes(&self) -> [u8; 4] {
+        self.0
+    }
+
+    /// Checks whether this is the [`NONE`](`FourCc::NONE`) code.
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Gets the code as a string, including trailing spaces. Returns an empty string for [`NONE`](`FourCc::NONE`).
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl Default for FourCc {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl Display for FourCc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Checks whether two 4 character codes refer to the same group, ignoring trailing spaces.
+///
+/// This allows `"mat"` to match the stored `"mat "` code.
+pub(crate) fn fourcc_matches(fourcc: &str, group: &str) -> bool {
+    fourcc.trim_end_matches(' ') == group.trim_end_matches(' ')
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Group of a tag, which determines how the rest of the tag is read.
+///
+/// Groups are stored as 4 character codes, padded with trailing spaces (for instance `"mat "`). Matching on this enum
+/// avoids comparing against the raw string, where a missing trailing space silently fails to match.
+pub enum TagGroup {
+    /// `mat `: Material, describing the shader and parameters used to render a surface.
+    Material,
+    /// `bitm`: Bitmap (texture).
+    Bitmap,
+    /// `mode`: Render model.
+    RenderModel,
+    /// `coll`: Collision model.
+    CollisionModel,
+    /// `hlmt`: Model, tying together the render, collision and physics models of an object.
+    Model,
+    /// `hsc*`: Compiled Lua script.
+    LuaScript,
+    /// `sbsp`: Structure BSP, containing the geometry of a level.
+    StructureBsp,
+    /// `effe`: Effect.
+    Effect,
+    /// `prt3`: Particle.
+    Particle,
+    /// `pmdf`: Particle model.
+    ParticleModel,
+    /// `unic`: Multilingual unicode string list.
+    MultilingualUnicodeStringList,
+    /// Any other group, holding its raw 4 character code.
+    Unknown(String),
+}
+
+impl Default for TagGroup {
+    fn default() -> Self {
+        Self::Unknown(String::new())
+    }
+}
+
+impl TagGroup {
+    /// Gets the group matching a 4 character code.
+    ///
+    /// Trailing spaces are optional, so both `"mat "` and `"mat"` return [`TagGroup::Material`].
+    ///
+    /// # Arguments
+    ///
+    /// * `fourcc` - The 4 character code of the group, as stored in [`tag_group`](`crate::module::file::ModuleFileEntry::tag_group`).
+    ///
+    /// # Returns
+    ///
+    /// The matching group, or [`TagGroup::Unknown`] holding `fourcc` unchanged if the group is not known.
+    #[must_use]
+    pub fn from_fourcc(fourcc: &str) -> Self {
+        match fourcc.trim_end_matches(' ') {
+            "mat" => Self::Material,
+            "bitm" => Self::Bitmap,
+            "mode" => Self::RenderModel,
+            "coll" => Self::CollisionModel,
+            "hlmt" => Self::Model,
+            "hsc*" => Self::LuaScript,
+            "sbsp" => Self::StructureBsp,
+            "effe" => Self::Effect,
+            "prt3" => Self::Particle,
+            "pmdf" => Self::ParticleModel,
+            "unic" => Self::MultilingualUnicodeStringList,
+            _ => Self::Unknown(fourcc.to_string()),
+        }
+    }
+
+    /// Gets the 4 character code of the group, including trailing spaces.
+    ///
+    /// The result can be compared against [`tag_group`](`crate::module::file::ModuleFileEntry::tag_group`), or passed
+    /// to functions taking a group such as [`iter_metadata`](`crate::ModuleFile::iter_metadata`).
+    #[must_use]
+    pub fn as_fourcc(&self) -> &str {
+        match self {
+            Self::Material => "mat ",
+            Self::Bitmap => "bitm",
+            Self::RenderModel => "mode",
+            Self::CollisionModel => "coll",
+            Self::Model => "hlmt",
+            Self::LuaScript => "hsc*",
+            Self::StructureBsp => "sbsp",
+            Self::Effect => "effe",
+            Self::Particle => "prt3",
+            Self::ParticleModel => "pmdf",
+            Self::MultilingualUnicodeStringList => "unic",
+            Self::Unknown(fourcc) => fourcc,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::file::ModuleFileEntry;
+    use crate::tag::dependency::TagDependency;
+    use crate::tag::types::common_types::FieldReference;
+    use std::io::Cursor;
+
+    #[test]
+    /// Verifies that codes are reversed from their stored order, and that the empty code is handled.
+    fn test_fourcc() {
+        let fourcc = FourCc::read(&mut Cursor::new(b" tam")).unwrap();
+        assert_eq!(fourcc.as_str(), "mat ");
+        assert_eq!(fourcc.bytes(), *b"mat ");
+
+        let fourcc = FourCc::read(&mut Cursor::new(b"mtib")).unwrap();
+        assert_eq!(fourcc.to_string(), "bitm");
+        assert_eq!(TagGroup::from_fourcc(fourcc.as_str()), TagGroup::Bitmap);
+
+        let fourcc = FourCc::read(&mut Cursor::new([0xFF; 4])).unwrap();
+        assert!(fourcc.is_none());
+        assert_eq!(fourcc.as_str(), "");
+
+        assert!(FourCc::read(&mut Cursor::new([0xFF, 0xFE, 0x20, 0x20])).is_err());
+    }
+
+    #[test]
+    /// Verifies that trailing spaces are optional when matching groups, and kept when converting back.
+    fn test_trailing_spaces() {
+        assert_eq!(TagGroup::from_fourcc("mat "), TagGroup::Material);
+        assert_eq!(TagGroup::from_fourcc("mat"), TagGroup::Material);
+        assert_eq!(TagGroup::Material.as_fourcc(), "mat ");
+    }
+
+    #[test]
+    /// Verifies that groups are compared without their trailing spaces, for each type holding a group.
+    fn test_is_group() {
+        assert!(fourcc_matches("mat ", "mat"));
+        assert!(fourcc_matches("mat ", "mat "));
+        assert!(!fourcc_matches("mat ", "bitm"));
+        assert!(!fourcc_matches("mat ", "ma"));
+
+        let mut entry = ModuleFileEntry::default();
+        entry.tag_group = "mat ".to_string();
+        assert!(entry.is_group("mat"));
+        assert!(entry.is_group("mat "));
+        assert!(!entry.is_group("bitm"));
+
+        let dependency = TagDependency {
+            tag_group: "bitm".to_string(),
+            ..Default::default()
+        };
+        assert!(dependency.is_group("bitm"));
+        assert!(!dependency.is_group("mat"));
+
+        let mut reference = FieldReference::default();
+        reference.group = "mat ".to_string();
+        assert!(reference.is_group("mat"));
+        assert!(!reference.is_group("bitm"));
+    }
+
+    #[test]
+    /// Verifies that unknown groups keep their raw code.
+    fn test_unknown() {
+        let group = TagGroup::from_fourcc("zzz ");
+        assert_eq!(group, TagGroup::Unknown("zzz ".to_string()));
+        assert_eq!(group.as_fourcc(), "zzz ");
+        assert_eq!(TagGroup::from_fourcc("bitm").as_fourcc(), "bitm");
+    }
+}
@@ -19,7 +19,7 @@ pub struct TagHeader {
     version: i32,
     /// Secondary GUID to identify the root structure.
     pub root_struct_guid: i64,
-    /// Checksum generated from unknown algorithm
+    /// Checksum generated from unknown algorithm, see [`TagFile::checksum`](`crate::tag::loader::TagFile::checksum`).
     pub checksum: i64,
     /// Number of tags required to load tag.
     pub dependency_count: u32,
@@ -34,8 +34,7 @@ pub struct TagHeader {
     /// Size in bytes of string table inside tag.
     /// Unused after Halo 5.
     pub string_table_size: u32,
-    /// Size in bytes of "zoneset" section of tag.
-    /// Unknown use.
+    /// Size in bytes of "zoneset" section of tag, see [`TagZoneset`](`crate::tag::zoneset::TagZoneset`).
     pub zoneset_size: u32,
     /// Unknown. Possibly used to split something in memory.
     unknown: u32,
@@ -3,12 +3,33 @@
 use std::io::SeekFrom;
 
 use super::{
-    data_reference::TagDataReference, datablock::TagDataBlock, dependency::TagDependency,
-    header::TagHeader, reference::TagReference, structure::TagStruct,
+    data_reference::TagDataReference,
+    datablock::{TagDataBlock, TagSectionType},
+    dependency::TagDependency,
+    header::TagHeader,
+    reference::TagReference,
+    structure::{TagStruct, TagStructType},
+    zoneset::TagZoneset,
 };
-use crate::common::extensions::BufReaderExt;
+use crate::common::errors::TagError;
+use crate::common::extensions::{BufReaderExt, ByteReader, Enumerable};
+use crate::common::ids::TagId;
+use crate::common::logging;
 use crate::module::header::ModuleVersion;
-use crate::Result;
+use crate::{Error, Result};
+
+/// Size of [`TagHeader`] in bytes.
+const TAG_HEADER_SIZE: i64 = 0x50;
+/// Size of [`TagDependency`] in bytes.
+const TAG_DEPENDENCY_SIZE: i64 = 0x18;
+/// Size of [`TagDataBlock`] in bytes.
+const TAG_DATABLOCK_SIZE: i64 = 0x10;
+/// Size of [`TagStruct`] in bytes.
+const TAG_STRUCT_SIZE: i64 = 0x20;
+/// Size of [`TagDataReference`] in bytes.
+const TAG_DATA_REFERENCE_SIZE: i64 = 0x14;
+/// Size of [`TagReference`] in bytes.
+const TAG_REFERENCE_SIZE: i64 = 0x10;
 
 #[derive(Default, Debug)]
 /// Tag structure containing structure of entire tag file.
@@ -70,8 +91,533 @@ impl TagFile {
                 reference.name = Some(reader.read_null_terminated_string()?);
             }
         }
+        // Mismatches are only logged, use `validate` to make table overruns an error.
+        let padding = self.header_padding();
+        if padding != 0 {
+            logging::debug!(
+                padding,
+                "tag header size does not match the size of its tables"
+            );
+        }
         // Ensure that tag data starts where it is supposed to.
         reader.seek(SeekFrom::Start(u64::from(self.header.header_size)))?;
         Ok(())
     }
+
+    /// Gets the difference between [`header_size`](`TagHeader::header_size`) and the size of the header and tables.
+    ///
+    /// The tables (dependencies, datablocks, structs, data references and tag references) are followed by the string table
+    /// and zoneset data, after which the tag data should start. A non-zero value means that the header contains data
+    /// that is not read (if positive) or that the counts in the header are incorrect (if negative).
+    #[must_use]
+    pub fn header_padding(&self) -> i64 {
+        let header = &self.header;
+        let tables_size = self.string_table_offset()
+            + i64::from(header.string_table_size)
+            + i64::from(header.zoneset_size);
+        i64::from(header.header_size) - tables_size
+    }
+
+    /// Checks that the tables, string table and zoneset section of the header fit in its
+    /// [`header_size`](`TagHeader::header_size`).
+    ///
+    /// [`read`](`TagFile::read`) always continues at `header_size`, and only logs a mismatch between the two. Table counts
+    /// that are too large, usually caused by corruption, can be detected here instead. The end of the tables is computed
+    /// from the counts and sizes in the header.
+    ///
+    /// # Errors
+    /// - If the tables of the header extend past its size [`TagError::TableOverrun`]
+    pub fn validate(&self) -> Result<()> {
+        let padding = self.header_padding();
+        if padding < 0 {
+            let tables_end = u64::try_from(i64::from(self.header.header_size) - padding)?;
+            return Err(Error::TagError(TagError::TableOverrun(
+                tables_end,
+                self.header.header_size,
+            )));
+        }
+        Ok(())
+    }
+
+    /// Gets the offset of the string table from the start of the tag file, computed from the counts of the tables before it.
+    fn string_table_offset(&self) -> i64 {
+        let header = &self.header;
+        TAG_HEADER_SIZE
+            + i64::from(header.dependency_count) * TAG_DEPENDENCY_SIZE
+            + i64::from(header.datablock_count) * TAG_DATABLOCK_SIZE
+            + i64::from(header.tagstruct_count) * TAG_STRUCT_SIZE
+            + i64::from(header.data_reference_count) * TAG_DATA_REFERENCE_SIZE
+            + i64::from(header.tag_reference_count) * TAG_REFERENCE_SIZE
+    }
+
+    /// Reads the tag file from a buffer containing the decompressed data of a tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The decompressed tag data, starting with the tag header.
+    /// * `version` - Version of the module the tag was stored in.
+    ///
+    /// # Errors
+    /// - If the tag fails to be read [`TagFile::read`]
+    pub fn from_bytes(data: &[u8], version: &ModuleVersion) -> Result<Self> {
+        let mut tag = Self::default();
+        tag.read(&mut ByteReader::new(data), version)?;
+        Ok(tag)
+    }
+
+    /// Returns the tags this tag depends on.
+    ///
+    /// Dependencies are tags that are lazy loaded by the game when this tag is accessed, for instance the
+    /// shader and bitmaps of a material. Each entry contains the global tag id of the referenced tag, which can
+    /// be resolved using [`read_tag_from_id`](`crate::ModuleFile::read_tag_from_id`). Referenced tags are not
+    /// guaranteed to be in the same module, see [`prefetch_dependencies`](`crate::ModuleFile::prefetch_dependencies`).
+    #[must_use]
+    pub fn dependencies(&self) -> &[TagDependency] {
+        &self.dependencies
+    }
+
+    /// Gets the global tag ID and group of each dependency of the tag.
+    ///
+    /// Dependencies without a tag ID (-1) are skipped. The tag IDs can be resolved across modules using
+    /// [`ModuleSet::find_tag`](`crate::module::set::ModuleSet::find_tag`) or
+    /// [`ModuleSet::read_tag_from_id`](`crate::module::set::ModuleSet::read_tag_from_id`).
+    #[must_use]
+    pub fn dependency_refs(&self) -> Vec<(TagId, &str)> {
+        self.dependencies
+            .iter()
+            .filter(|dependency| dependency.tag_id != -1)
+            .map(|dependency| (TagId(dependency.tag_id), dependency.tag_group.as_str()))
+            .collect()
+    }
+
+    /// Gets the checksum stored in the header of the tag.
+    ///
+    /// The algorithm used to generate the checksum is unknown. Collecting checksums along with the data of the tags they
+    /// belong to can help finding it.
+    #[must_use]
+    pub fn checksum(&self) -> i64 {
+        self.header.checksum
+    }
+
+    /// Verifies the checksum of the tag against its data.
+    ///
+    /// This is a placeholder until the checksum algorithm is known, and currently always fails.
+    ///
+    /// # Errors
+    /// - As the checksum algorithm is unknown [`TagError::ChecksumUnsupported`]
+    #[allow(clippy::unused_self)]
+    pub fn verify_checksum(&self) -> Result<bool> {
+        Err(Error::TagError(TagError::ChecksumUnsupported))
+    }
+
+    /// Parses the zoneset section of the tag, stored in the [`zoneset_size`](`TagHeader::zoneset_size`) bytes after
+    /// the string table.
+    ///
+    /// The layout of the section has not been verified against game files, so it is only parsed when requested
+    /// and is not part of [`read`](`TagFile::read`). An empty [`TagZoneset`] is returned if the tag has no zoneset section.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Decompressed data of the tag, starting with the tag header (for instance the contents of
+    ///   [`data_stream`](`crate::module::file::ModuleFileEntry::data_stream`)).
+    ///
+    /// # Errors
+    /// - If the zoneset section is outside of `data` [`TagError::DataOutOfBounds`]
+    /// - If the zonesets extend past the end of the section [`ReadError`](`crate::Error::ReadError`)
+    pub fn zonesets(&self, data: &[u8]) -> Result<TagZoneset> {
+        let mut zoneset = TagZoneset::default();
+        if self.header.zoneset_size == 0 {
+            return Ok(zoneset);
+        }
+        let start =
+            usize::try_from(self.string_table_offset() + i64::from(self.header.string_table_size))?;
+        let section = start
+            .checked_add(self.header.zoneset_size as usize)
+            .and_then(|end| data.get(start..end))
+            .ok_or(Error::TagError(TagError::DataOutOfBounds))?;
+        zoneset.read(&mut ByteReader::new(section))?;
+        Ok(zoneset)
+    }
+
+    /// Gets the global tag IDs of the tags in each zoneset of the tag.
+    ///
+    /// The outer list is in the order of [`zonesets`](`TagZoneset::zonesets`), and can be matched to a zoneset by its
+    /// [`string_id`](`super::zoneset::TagZonesetInstanceHeader::string_id`). Footer tags are not included.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Decompressed data of the tag, starting with the tag header.
+    ///
+    /// # Errors
+    /// - If the zoneset section fails to be parsed [`zonesets`](`TagFile::zonesets`)
+    pub fn zoneset_tag_ids(&self, data: &[u8]) -> Result<Vec<Vec<TagId>>> {
+        Ok(self
+            .zonesets(data)?
+            .zonesets
+            .iter()
+            .map(|zoneset| {
+                zoneset
+                    .tags
+                    .iter()
+                    .map(|tag| TagId(tag.global_id))
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Finds a struct definition by its GUID.
+    ///
+    /// # Arguments
+    ///
+    /// * `guid` - The GUID of the struct definition to find.
+    #[must_use]
+    pub fn struct_by_guid(&self, guid: u128) -> Option<&TagStruct> {
+        self.struct_definitions.iter().find(|s| s.guid == guid)
+    }
+
+    /// Gets every root ([`MainStruct`](`TagStructType::MainStruct`)) struct definition of the tag, in order.
+    ///
+    /// Most tags have a single root, but some legitimately contain more than one. Unlike
+    /// [`main_struct`](`TagFile::main_struct`), which picks one of them, this allows reading each root manually.
+    #[must_use]
+    pub fn roots(&self) -> Vec<&TagStruct> {
+        self.struct_definitions
+            .iter()
+            .filter(|s| s.struct_type == TagStructType::MainStruct)
+            .collect()
+    }
+
+    /// Finds the root ([`MainStruct`](`TagStructType::MainStruct`)) struct definition of the tag.
+    ///
+    /// Some tags contain more than one top-level struct. In that case, [`root_struct_guid`](`TagHeader::root_struct_guid`)
+    /// is used to pick one of them.
+    ///
+    /// This is a heuristic: how the 64-bit header GUID relates to the 128-bit struct GUIDs has not been verified, so it
+    /// is compared against both halves of each struct GUID, and the first candidate is returned if none match. Use
+    /// [`roots`](`TagFile::roots`) to choose between the candidates manually.
+    #[must_use]
+    pub fn main_struct(&self) -> Option<&TagStruct> {
+        let mut candidates = self
+            .struct_definitions
+            .iter()
+            .filter(|s| s.struct_type == TagStructType::MainStruct);
+        let first = candidates.next()?;
+        #[allow(clippy::cast_sign_loss)]
+        let root_guid = self.header.root_struct_guid as u64;
+        let matches_root = |s: &TagStruct| {
+            #[allow(clippy::cast_possible_truncation)]
+            let (low, high) = (s.guid as u64, (s.guid >> 64) as u64);
+            low == root_guid || high == root_guid
+        };
+        if matches_root(first) {
+            return Some(first);
+        }
+        Some(candidates.find(|s| matches_root(s)).unwrap_or(first))
+    }
+
+    /// Gets the datablock at `index` in [`datablock_definitions`](`TagFile::datablock_definitions`), as pointed to by
+    /// the [`target_index`](`TagStruct::target_index`) of a struct.
+    ///
+    /// # Errors
+    /// - If the index is negative or past the last datablock [`TagError::BlockIndexOutOfRange`]
+    pub fn datablock(&self, index: i32) -> Result<&TagDataBlock> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.datablock_definitions.get(index))
+            .ok_or(Error::TagError(TagError::BlockIndexOutOfRange(index)))
+    }
+
+    /// Gets the offset of a section from the start of the tag file.
+    ///
+    /// Sections are laid out sequentially: header, tag data, resource data and "actual" resource data.
+    /// [`TagDataBlock::offset`] is relative to the start of the section the block is stored in.
+    ///
+    /// # Arguments
+    ///
+    /// * `section_type` - The section to get the offset of.
+    #[must_use]
+    pub fn section_offset(&self, section_type: &TagSectionType) -> u64 {
+        let header_size = u64::from(self.header.header_size);
+        let data_size = u64::from(self.header.data_size);
+        let resource_size = u64::from(self.header.resource_size);
+        match section_type {
+            TagSectionType::Header => 0,
+            TagSectionType::TagData => header_size,
+            TagSectionType::ResourceData => header_size + data_size,
+            TagSectionType::ActualResource => header_size + data_size + resource_size,
+        }
+    }
+
+    /// Gets the offset of a datablock from the start of the tag file.
+    ///
+    /// [`TagDataBlock::offset`] is stored relative to the start of its section, so the offset of the section
+    /// is added depending on the [`section_type`](`TagDataBlock::section_type`) of the block.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The datablock to get the offset of.
+    #[must_use]
+    pub fn absolute_offset(&self, block: &TagDataBlock) -> u64 {
+        self.section_offset(&block.section_type) + block.offset
+    }
+
+    /// Finds the data reference for a field located at the given offset in the tag data section.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_offset` - Offset of the field from the start of the tag data section.
+    #[must_use]
+    pub fn data_reference_at(&self, field_offset: u64) -> Option<&TagDataReference> {
+        self.datablock_definitions
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| {
+                block.section_type == TagSectionType::TagData
+                    && (block.offset..block.offset + u64::from(block.entry_size))
+                        .contains(&field_offset)
+            })
+            .find_map(|(index, block)| {
+                let field_block = i32::try_from(index).ok()?;
+                self.data_references.iter().find(|reference| {
+                    reference.field_block == field_block
+                        && u64::from(reference.field_offset) == field_offset - block.offset
+                })
+            })
+    }
+
+    /// Gets the blob of bytes pointed to by a data reference, such as a variable-length string.
+    ///
+    /// The blob is the whole datablock at the [`target_index`](`TagDataReference::target_index`) of the reference.
+    ///
+    /// # Arguments
+    ///
+    /// * `ref_index` - Index of the reference in [`data_references`](`TagFile::data_references`).
+    /// * `data` - Decompressed data of the tag, starting with the tag header (for instance the contents of
+    ///   [`data_stream`](`crate::module::file::ModuleFileEntry::data_stream`)).
+    ///
+    /// # Returns
+    ///
+    /// Returns [`None`] if the reference does not exist, is null (`target_index` of -1), or points outside of `data`.
+    #[must_use]
+    pub fn read_data_reference<'a>(&self, ref_index: usize, data: &'a [u8]) -> Option<&'a [u8]> {
+        let reference = self.data_references.get(ref_index)?;
+        let block = self.datablock(reference.target_index).ok()?;
+        let start = usize::try_from(self.absolute_offset(block)).ok()?;
+        data.get(start..start + block.entry_size as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{common::errors::TagError, Error};
+
+    fn main_struct(guid: u128, target_index: i32) -> TagStruct {
+        let mut tag_struct = TagStruct::default();
+        tag_struct.guid = guid;
+        tag_struct.struct_type = TagStructType::MainStruct;
+        tag_struct.target_index = target_index;
+        tag_struct
+    }
+
+    #[test]
+    /// Verifies that `root_struct_guid` is used to choose between multiple root candidates.
+    fn test_main_struct_multiple_roots() {
+        let mut tag = TagFile {
+            struct_definitions: vec![
+                main_struct(0x1111_2222_3333_4444_5555_6666_7777_8888, 0),
+                main_struct(0xAAAA_BBBB_CCCC_DDDD_0000_0000_0000_0042, 1),
+            ],
+            ..Default::default()
+        };
+        tag.header.root_struct_guid = 0x42;
+
+        let root = tag.main_struct().unwrap();
+        assert_eq!(root.target_index, 1);
+        assert_eq!(
+            tag.struct_by_guid(0x1111_2222_3333_4444_5555_6666_7777_8888)
+                .map(|s| s.target_index),
+            Some(0)
+        );
+
+        tag.header.root_struct_guid = 0;
+        assert_eq!(tag.main_struct().unwrap().target_index, 0);
+    }
+
+    #[test]
+    /// Verifies that every root struct is returned in order, ignoring other struct types.
+    fn test_roots() {
+        let mut block = main_struct(3, 2);
+        block.struct_type = TagStructType::TagBlock;
+        let tag = TagFile {
+            struct_definitions: vec![main_struct(1, 0), block, main_struct(2, 1)],
+            ..Default::default()
+        };
+        let roots: Vec<i32> = tag.roots().iter().map(|s| s.target_index).collect();
+        assert_eq!(roots, [0, 1]);
+        assert!(TagFile::default().roots().is_empty());
+    }
+
+    #[test]
+    /// Verifies that dependencies are returned with their group, skipping the ones without a tag ID.
+    fn test_dependency_refs() {
+        let tag = TagFile {
+            dependencies: [(0x10, "mat "), (-1, "bitm"), (0x20, "bitm")]
+                .into_iter()
+                .map(|(tag_id, tag_group)| TagDependency {
+                    tag_id,
+                    tag_group: tag_group.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            tag.dependency_refs(),
+            [(TagId(0x10), "mat "), (TagId(0x20), "bitm")]
+        );
+    }
+
+    #[test]
+    /// Verifies that the checksum is exposed, and that verifying it is reported as unsupported.
+    fn test_checksum() {
+        let mut tag = TagFile::default();
+        tag.header.checksum = -0x1234;
+        assert_eq!(tag.checksum(), -0x1234);
+        assert!(matches!(
+            tag.verify_checksum(),
+            Err(Error::TagError(TagError::ChecksumUnsupported))
+        ));
+    }
+
+    #[test]
+    /// Verifies that datablock offsets are converted from section-relative to absolute offsets for each section.
+    fn test_absolute_offset() {
+        let mut tag = TagFile::default();
+        tag.header.header_size = 0x100;
+        tag.header.data_size = 0x200;
+        tag.header.resource_size = 0x40;
+
+        let mut block = TagDataBlock::default();
+        block.offset = 0x10;
+        for (section_type, expected) in [
+            (TagSectionType::Header, 0x10),
+            (TagSectionType::TagData, 0x110),
+            (TagSectionType::ResourceData, 0x310),
+            (TagSectionType::ActualResource, 0x350),
+        ] {
+            block.section_type = section_type;
+            assert_eq!(tag.absolute_offset(&block), expected);
+        }
+    }
+
+    #[test]
+    /// Verifies that data references return the bytes of their target datablock, and nothing for null or invalid ones.
+    fn test_read_data_reference() {
+        let mut tag = TagFile::default();
+        tag.header.header_size = 0x10;
+        tag.header.data_size = 0x10;
+        let mut block = TagDataBlock::default();
+        block.entry_size = 4;
+        block.section_type = TagSectionType::TagData;
+        block.offset = 8;
+        tag.datablock_definitions.push(block);
+        for target_index in [0, -1, 1] {
+            let mut reference = TagDataReference::default();
+            reference.target_index = target_index;
+            tag.data_references.push(reference);
+        }
+
+        let data: Vec<u8> = (0..0x20).collect();
+        assert_eq!(
+            tag.read_data_reference(0, &data),
+            Some([0x18, 0x19, 0x1A, 0x1B].as_slice())
+        );
+        assert_eq!(tag.read_data_reference(0, &data[..0x1A]), None);
+        assert_eq!(tag.read_data_reference(1, &data), None);
+        assert_eq!(tag.read_data_reference(2, &data), None);
+        assert_eq!(tag.read_data_reference(3, &data), None);
+    }
+
+    #[test]
+    /// Verifies that tags are read from byte buffers, and that corrupted counts return an error instead of allocating.
+    fn test_from_bytes() {
+        let mut data = include_bytes!("../../fuzz/corpus/fuzz_tag/struct_and_block").to_vec();
+        let tag = TagFile::from_bytes(&data, &ModuleVersion::Season3).unwrap();
+        assert_eq!(tag.datablock_definitions.len(), 1);
+        assert_eq!(tag.main_struct().unwrap().target_index, 0);
+
+        // Set the dependency count to u32::MAX.
+        data[0x18..0x1C].copy_from_slice(&[0xFF; 4]);
+        assert!(TagFile::from_bytes(&data, &ModuleVersion::Season3).is_err());
+    }
+
+    #[test]
+    /// Verifies that tables extending past the header size are still read, and reported by `validate`.
+    fn test_table_overrun() {
+        let mut data = include_bytes!("../../fuzz/corpus/fuzz_tag/struct_and_block").to_vec();
+        assert!(TagFile::from_bytes(&data, &ModuleVersion::Season3)
+            .unwrap()
+            .validate()
+            .is_ok());
+
+        data.resize(data.len() + 0x20, 0);
+        // Set the struct count to 2, so that the tables end 0x20 bytes after the header.
+        data[0x20..0x24].copy_from_slice(&2u32.to_le_bytes());
+        let tag = TagFile::from_bytes(&data, &ModuleVersion::Season3).unwrap();
+        assert_eq!(tag.struct_definitions.len(), 2);
+        assert!(matches!(
+            tag.validate(),
+            Err(Error::TagError(TagError::TableOverrun(0xA0, 0x80)))
+        ));
+    }
+
+    #[test]
+    /// Verifies that zonesets are only parsed on request, from the bytes after the string table.
+    fn test_zonesets() {
+        let mut tag = TagFile::default();
+        assert!(tag.zoneset_tag_ids(&[]).unwrap().is_empty());
+
+        // Header of 0x50 bytes and a string table of 4 bytes, followed by the zoneset section.
+        let mut data = vec![0u8; 0x54];
+        for value in [1i32, 1, 0, 0, 0x10, 2, 0, 0, 100, 0x10, 101, 0x10] {
+            // version, zoneset count, footer count, parent count, string id, tag count, parent count, footer count, tags
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        tag.header.string_table_size = 4;
+        tag.header.zoneset_size = 0x30;
+        assert_eq!(
+            tag.zoneset_tag_ids(&data).unwrap(),
+            [[TagId(100), TagId(101)]]
+        );
+
+        assert!(matches!(
+            tag.zonesets(&data[..0x80]),
+            Err(Error::TagError(TagError::DataOutOfBounds))
+        ));
+        // Counts extending past the section are an error, rather than reading the data after it.
+        tag.header.zoneset_size = 0x20;
+        assert!(matches!(tag.zonesets(&data), Err(Error::ReadError(_))));
+    }
+
+    #[test]
+    /// Verifies that the header padding is zero when the header size matches the tables.
+    fn test_header_padding() {
+        let data = include_bytes!("../../fuzz/corpus/fuzz_tag/struct_and_block");
+        let mut tag = TagFile::from_bytes(data, &ModuleVersion::Season3).unwrap();
+        assert_eq!(tag.header_padding(), 0);
+
+        tag.header.header_size += 8;
+        assert_eq!(tag.header_padding(), 8);
+    }
+
+    #[test]
+    /// Verifies that parse errors are returned as the typed crate error.
+    fn test_parse_error_is_typed() {
+        let result: Result<TagFile> = TagFile::from_bytes(&[0u8; 0x50], &ModuleVersion::Season3);
+        assert!(matches!(
+            result,
+            Err(Error::TagError(TagError::IncorrectMagic(0)))
+        ));
+    }
 }
@@ -3,8 +3,10 @@
 pub mod data_reference;
 pub mod datablock;
 pub mod dependency;
+pub mod group;
 pub mod header;
 pub mod loader;
 pub mod reference;
 pub mod structure;
 pub mod types;
+pub mod zoneset;
@@ -32,7 +32,7 @@ pub struct TagStruct {
     pub guid: u128,
     /// Where the structure is located.
     pub struct_type: TagStructType,
-    /// Unknown (but important)
+    /// Unknown (but important), see [`unknown`](`TagStruct::unknown`).
     unknown: u16,
     /// For main struct and tag block structs, the index of the block containing the struct.
     /// For resource structs, index of the resource.
@@ -57,3 +57,36 @@ impl Enumerable for TagStruct {
         Ok(())
     }
 }
+
+impl TagStruct {
+    /// Gets the unknown value stored after the [`struct_type`](`TagStruct::struct_type`).
+    ///
+    /// The meaning of this value is not known yet. It is exposed so that values can be collected across tags to
+    /// determine it, and should not be relied on: this getter may be replaced by a typed field once it is understood.
+    #[must_use]
+    pub fn unknown(&self) -> u16 {
+        self.unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    /// Verifies that the unknown value is read between the struct type and the target index.
+    fn test_unknown() {
+        let mut data = vec![0; 0x10]; // guid
+        data.extend_from_slice(&1u16.to_le_bytes()); // tag block
+        data.extend_from_slice(&0x1234u16.to_le_bytes());
+        data.extend_from_slice(&2i32.to_le_bytes());
+        data.extend_from_slice(&[0; 8]);
+
+        let mut tag_struct = TagStruct::default();
+        tag_struct.read(&mut Cursor::new(data)).unwrap();
+        assert_eq!(tag_struct.struct_type, TagStructType::TagBlock);
+        assert_eq!(tag_struct.unknown(), 0x1234);
+        assert_eq!(tag_struct.target_index, 2);
+    }
+}
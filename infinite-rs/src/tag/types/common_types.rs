@@ -3,6 +3,7 @@
 use byteorder::{ReadBytesExt, LE};
 use num_enum::TryFromPrimitive;
 use std::{
+    f32::consts::{PI, TAU},
     fmt::Debug,
     io::{BufRead, Seek, SeekFrom},
 };
@@ -13,12 +14,23 @@ use crate::{
 };
 use crate::{
     common::extensions::BufReaderExt,
-    module::file::TagStructure,
-    tag::{datablock::TagDataBlock, structure::TagStruct},
+    module::{file::TagStructure, header::ModuleVersion},
+    tag::{
+        datablock::TagDataBlock,
+        group::{fourcc_matches, FourCc},
+        structure::TagStruct,
+    },
 };
 
+/// Gets the part of a fixed-length string read from a tag up to its first null character.
+fn trim_nul(raw: &str) -> &str {
+    raw.split('\0').next().unwrap_or_default()
+}
+
 #[derive(Default, Debug)]
 /// _0: 32 Byte strings that usually store some sort of short name.
+///
+/// The inner string contains all 32 bytes as stored in the tag, including null bytes.
 pub struct FieldString(pub String);
 
 impl FieldString {
@@ -26,10 +38,24 @@ impl FieldString {
         self.0 = reader.read_fixed_string(32)?;
         Ok(())
     }
+
+    /// Gets the string up to the first null byte.
+    #[must_use]
+    pub fn trimmed(&self) -> &str {
+        trim_nul(&self.0)
+    }
+
+    /// Gets the exact bytes of the string as stored in the tag, including any data after the first null byte.
+    #[must_use]
+    pub fn raw_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
 }
 
 #[derive(Default, Debug)]
 /// _1: 256 byte long string usually used to store paths.
+///
+/// The inner string contains all 256 bytes as stored in the tag, including null bytes.
 pub struct FieldLongString(pub String);
 
 impl FieldLongString {
@@ -37,6 +63,38 @@ impl FieldLongString {
         self.0 = reader.read_fixed_string(256)?;
         Ok(())
     }
+
+    /// Gets the string up to the first null byte.
+    #[must_use]
+    pub fn trimmed(&self) -> &str {
+        trim_nul(&self.0)
+    }
+
+    /// Gets the exact bytes of the string as stored in the tag, including any data after the first null byte.
+    #[must_use]
+    pub fn raw_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+#[derive(Default, Debug)]
+/// Fixed-length string of `N` UTF-16 characters, used by localized text and some path fields.
+///
+/// Tag definitions have no dedicated field type for wide strings, so this can be used in custom structures in place of
+/// the raw data. The inner string contains all `N` characters as stored in the tag, including null characters.
+pub struct FieldWideString<const N: usize = 256>(pub String);
+
+impl<const N: usize> FieldWideString<N> {
+    pub fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+        self.0 = reader.read_fixed_string_utf16le(N)?;
+        Ok(())
+    }
+
+    /// Gets the string up to the first null character.
+    #[must_use]
+    pub fn trimmed(&self) -> &str {
+        trim_nul(&self.0)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -48,6 +106,15 @@ impl FieldStringId {
         self.0 = reader.read_i32::<LE>()?;
         Ok(())
     }
+
+    /// Checks whether the string ID refers to a non-empty string.
+    ///
+    /// The hash of an empty string is zero, which is also used by fields that are left at their default (for instance
+    /// material style intentions that are not overridden).
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.0 != 0
+    }
 }
 
 #[derive(Default, Debug)]
@@ -103,6 +170,23 @@ impl FieldAngle {
         self.0 = reader.read_f32::<LE>()?;
         Ok(())
     }
+
+    /// Gets the angle in degrees. The angle is stored in radians.
+    #[must_use]
+    pub fn degrees(&self) -> f32 {
+        self.0.to_degrees()
+    }
+
+    /// Gets the angle in radians, wrapped to the range [-π, π).
+    #[must_use]
+    pub fn normalized(&self) -> f32 {
+        normalize_angle(self.0)
+    }
+}
+
+/// Wraps an angle in radians to the range [-π, π).
+fn normalize_angle(radians: f32) -> f32 {
+    (radians + PI).rem_euclid(TAU) - PI
 }
 
 #[derive(Default, Debug)]
@@ -174,32 +258,59 @@ impl<T: bitflags::Flags<Bits = u8>> FieldByteFlags<T> {
     }
 }
 
+/// 16-bit integer type used for the coordinates of [`FieldPoint2D`] and [`FieldRectangle2D`].
+///
+/// Both fields store 16-bit coordinates, which are read as [`u16`] by default. Coordinates that can be negative
+/// (for instance offsets relative to an anchor in UI tags) should be read as [`i16`] instead, as reading them as
+/// [`u16`] turns small negative values into large positive ones.
+pub trait Coordinate: Default + Debug {
+    /// Reads a coordinate from the reader.
+    ///
+    /// # Errors
+    /// - If the reader fails to read the exact number of bytes [`ReadError`](`crate::Error::ReadError`)
+    fn read_coordinate<R: BufRead>(reader: &mut R) -> Result<Self>;
+}
+
+impl Coordinate for u16 {
+    fn read_coordinate<R: BufRead>(reader: &mut R) -> Result<Self> {
+        Ok(reader.read_u16::<LE>()?)
+    }
+}
+
+impl Coordinate for i16 {
+    fn read_coordinate<R: BufRead>(reader: &mut R) -> Result<Self> {
+        Ok(reader.read_i16::<LE>()?)
+    }
+}
+
 #[derive(Default, Debug)]
 /// _10: X and Y coordinates of a point in 2D.
-pub struct FieldPoint2D {
-    pub x: u16,
-    pub y: u16,
+/// Use `FieldPoint2D<i16>` for signed coordinates (see [`Coordinate`]).
+pub struct FieldPoint2D<T: Coordinate = u16> {
+    pub x: T,
+    pub y: T,
 }
 
-impl FieldPoint2D {
+impl<T: Coordinate> FieldPoint2D<T> {
     pub fn read<R: BufRead>(&mut self, reader: &mut R) -> Result<()> {
-        self.x = reader.read_u16::<LE>()?;
-        self.y = reader.read_u16::<LE>()?;
+        self.x = T::read_coordinate(reader)?;
+        self.y = T::read_coordinate(reader)?;
         Ok(())
     }
 }
 
 #[derive(Default, Debug)]
 /// _11:  X and Y coordinates of a rectangle in 2D.
-pub struct FieldRectangle2D {
-    pub x: u16,
-    pub y: u16,
+/// Use `FieldRectangle2D<i16>` for signed coordinates (see [`Coordinate`]).
+pub struct FieldRectangle2D<T: Coordinate = u16> {
+    pub x: T,
+    pub y: T,
 }
 
-impl FieldRectangle2D {
+impl<T: Coordinate> FieldRectangle2D<T> {
     pub fn read<R: BufRead>(&mut self, reader: &mut R) -> Result<()> {
-        self.x = reader.read_u16::<LE>()?;
-        self.y = reader.read_u16::<LE>()?;
+        self.x = T::read_coordinate(reader)?;
+        self.y = T::read_coordinate(reader)?;
         Ok(())
     }
 }
@@ -505,6 +616,20 @@ impl FieldAngleBounds {
         self.max = reader.read_f32::<LE>()?;
         Ok(())
     }
+
+    /// Gets the minimum and maximum angles in degrees. The angles are stored in radians.
+    #[must_use]
+    pub fn degrees(&self) -> (f32, f32) {
+        (self.min.to_degrees(), self.max.to_degrees())
+    }
+
+    /// Gets the minimum and maximum angles in radians, each wrapped to the range [-π, π).
+    ///
+    /// As both angles are wrapped separately, the minimum can be larger than the maximum for bounds crossing π.
+    #[must_use]
+    pub fn normalized(&self) -> (f32, f32) {
+        (normalize_angle(self.min), normalize_angle(self.max))
+    }
 }
 
 #[derive(Default, Debug)]
@@ -570,6 +695,18 @@ impl FieldByteBlockFlags {
     }
 }
 
+/// Gets the element of `block` at `index`, or [`None`] if the index is `null` or outside of the block.
+fn resolve_block_index<T: TagStructure, I: Copy + PartialEq + TryInto<usize>>(
+    index: I,
+    null: I,
+    block: &FieldBlock<T>,
+) -> Option<&T> {
+    if index == null {
+        return None;
+    }
+    block.elements.get(index.try_into().ok()?)
+}
+
 #[derive(Default, Debug)]
 /// _2C: Char block index, stores an 8-bit unsigned integer.
 pub struct FieldCharBlockIndex(pub u8);
@@ -579,6 +716,14 @@ impl FieldCharBlockIndex {
         self.0 = reader.read_u8()?;
         Ok(())
     }
+
+    /// Gets the element of `block` that this index points to.
+    ///
+    /// Returns [`None`] if the index is null (`0xFF`) or outside of the block.
+    #[must_use]
+    pub fn resolve<'a, T: TagStructure>(&self, block: &'a FieldBlock<T>) -> Option<&'a T> {
+        resolve_block_index(self.0, u8::MAX, block)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -590,6 +735,14 @@ impl FieldCustomCharBlockIndex {
         self.0 = reader.read_u8()?;
         Ok(())
     }
+
+    /// Gets the element of `block` that this index points to.
+    ///
+    /// Returns [`None`] if the index is null (`0xFF`) or outside of the block.
+    #[must_use]
+    pub fn resolve<'a, T: TagStructure>(&self, block: &'a FieldBlock<T>) -> Option<&'a T> {
+        resolve_block_index(self.0, u8::MAX, block)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -601,6 +754,14 @@ impl FieldShortBlockIndex {
         self.0 = reader.read_u16::<LE>()?;
         Ok(())
     }
+
+    /// Gets the element of `block` that this index points to.
+    ///
+    /// Returns [`None`] if the index is null (`0xFFFF`) or outside of the block.
+    #[must_use]
+    pub fn resolve<'a, T: TagStructure>(&self, block: &'a FieldBlock<T>) -> Option<&'a T> {
+        resolve_block_index(self.0, u16::MAX, block)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -612,6 +773,14 @@ impl FieldCustomShortBlockIndex {
         self.0 = reader.read_u16::<LE>()?;
         Ok(())
     }
+
+    /// Gets the element of `block` that this index points to.
+    ///
+    /// Returns [`None`] if the index is null (`0xFFFF`) or outside of the block.
+    #[must_use]
+    pub fn resolve<'a, T: TagStructure>(&self, block: &'a FieldBlock<T>) -> Option<&'a T> {
+        resolve_block_index(self.0, u16::MAX, block)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -623,6 +792,14 @@ impl FieldLongBlockIndex {
         self.0 = reader.read_u32::<LE>()?;
         Ok(())
     }
+
+    /// Gets the element of `block` that this index points to.
+    ///
+    /// Returns [`None`] if the index is null (`0xFFFFFFFF`) or outside of the block.
+    #[must_use]
+    pub fn resolve<'a, T: TagStructure>(&self, block: &'a FieldBlock<T>) -> Option<&'a T> {
+        resolve_block_index(self.0, u32::MAX, block)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -634,6 +811,14 @@ impl FieldCustomLongBlockIndex {
         self.0 = reader.read_u32::<LE>()?;
         Ok(())
     }
+
+    /// Gets the element of `block` that this index points to.
+    ///
+    /// Returns [`None`] if the index is null (`0xFFFFFFFF`) or outside of the block.
+    #[must_use]
+    pub fn resolve<'a, T: TagStructure>(&self, block: &'a FieldBlock<T>) -> Option<&'a T> {
+        resolve_block_index(self.0, u32::MAX, block)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -658,6 +843,21 @@ impl FieldByteInteger {
     }
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Boolean stored as a single byte, where any non-zero value is `true`.
+///
+/// Tag definitions have no dedicated field type for booleans, so this can be used in custom structures in place of a
+/// [`FieldByteInteger`] or [`FieldCharEnum`] that only holds 0 or 1.
+pub struct FieldBool(pub bool);
+
+impl FieldBool {
+    pub fn read<R: BufRead>(&mut self, reader: &mut R) -> Result<()> {
+        self.0 = reader.read_u8()? != 0;
+        Ok(())
+    }
+}
+
 #[derive(Default, Debug)]
 /// _3D: Word integer field, stores a 16-bit unsigned integer.
 pub struct FieldWordInteger(pub u16);
@@ -699,6 +899,8 @@ pub struct FieldBlock<T: TagStructure> {
     unknown: u64,   // uintptr at runtime
     pub size: u32,
     pub elements: Vec<T>,
+    /// Whether the elements of the block were loaded by [`load_blocks`](`FieldBlock::load_blocks`).
+    loaded: bool,
 }
 
 impl<T: TagStructure + Debug + Default> FieldBlock<T> {
@@ -718,9 +920,11 @@ impl<T: TagStructure + Debug + Default> FieldBlock<T> {
         reader: &mut R,
         structs: &[TagStruct],
         blocks: &[TagDataBlock],
+        version: &ModuleVersion,
     ) -> Result<()> {
         // Empty blocks may cause issues.
         if self.size == 0 {
+            self.loaded = true;
             return Ok(());
         }
 
@@ -733,15 +937,19 @@ impl<T: TagStructure + Debug + Default> FieldBlock<T> {
         });
 
         if let Some(block_struct) = block_struct {
-            #[allow(clippy::cast_sign_loss)]
-            let block = &blocks[block_struct.target_index as usize];
+            let block = usize::try_from(block_struct.target_index)
+                .ok()
+                .and_then(|index| blocks.get(index))
+                .ok_or(Error::TagError(TagError::BlockIndexOutOfRange(
+                    block_struct.target_index,
+                )))?;
             let size = T::default().size();
 
             // We first read the object itself without any of its children
             reader.seek(SeekFrom::Start(block.offset))?;
             for _ in 0..self.size {
                 let mut object = T::default();
-                object.read(reader)?;
+                object.read_versioned(reader, version)?;
                 self.elements.push(object);
             }
 
@@ -754,15 +962,28 @@ impl<T: TagStructure + Debug + Default> FieldBlock<T> {
                     reader,
                     structs,
                     blocks,
+                    version,
                 )?;
             }
+            self.loaded = true;
         }
         Ok(())
     }
+
+    /// Checks whether the block was traversed when loading the tag.
+    ///
+    /// Unlike checking [`elements`](`FieldBlock::elements`), this distinguishes an empty block from one that was
+    /// skipped. A non-empty block is only skipped if no tag struct points to it, which usually means that the offset
+    /// of the field (or the size of a parent struct) is wrong.
+    #[must_use]
+    pub fn was_loaded(&self) -> bool {
+        self.loaded
+    }
 }
 
 #[derive(Default, Debug)]
 /// _41: Reference to an external tag.
+/// Takes up 0x1C bytes, with no padding between the fields.
 pub struct FieldReference {
     type_info: u64, // uintptr at runtime
     pub global_id: i32,
@@ -776,15 +997,38 @@ impl FieldReference {
         self.type_info = reader.read_u64::<LE>()?;
         self.global_id = reader.read_i32::<LE>()?;
         self.asset_id = reader.read_u64::<LE>()?;
-        self.group = reader.read_fixed_string(4)?.chars().rev().collect(); // reverse string
+        self.group = FourCc::read(reader)?.to_string();
         self.local_handle = reader.read_i32::<LE>()?;
         Ok(())
     }
+
+    /// Checks whether the referenced tag belongs to a group, ignoring trailing spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The 4 character code of the group, with or without trailing spaces.
+    #[must_use]
+    pub fn is_group(&self, group: &str) -> bool {
+        fourcc_matches(&self.group, group)
+    }
+
+    /// Handle of the referenced tag in the runtime tag table, -1 if not set.
+    #[must_use]
+    pub fn local_handle(&self) -> i32 {
+        self.local_handle
+    }
+
+    /// Runtime pointer to the type info of the reference. Only useful for debugging.
+    #[must_use]
+    pub fn type_info(&self) -> u64 {
+        self.type_info
+    }
 }
 
 #[derive(Default, Debug)]
 /// _42: "External" resource inside tag.
 pub struct FieldData {
+    pub(crate) field_offset: u64,
     data: u64,      // uintptr at runtime
     type_info: u64, // uintptr at runtime
     unknown: u32,   // always 0?
@@ -792,7 +1036,11 @@ pub struct FieldData {
 }
 
 impl FieldData {
-    pub fn read<R: BufRead>(&mut self, reader: &mut R) -> Result<()> {
+    /// Size of the field in bytes, including both runtime pointers.
+    pub const SIZE: u64 = 0x18;
+
+    pub fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+        self.field_offset = reader.stream_position()?;
         self.data = reader.read_u64::<LE>()?;
         self.type_info = reader.read_u64::<LE>()?;
         self.unknown = reader.read_u32::<LE>()?;
@@ -842,9 +1090,150 @@ pub struct AnyTag {
 }
 
 impl AnyTag {
+    /// Size of the field in bytes, including the runtime vtable pointer.
+    pub const SIZE: u64 = 0x10;
+
     pub fn read<R: BufRead>(&mut self, reader: &mut R) -> Result<()> {
         self.vtable_space = reader.read_u64::<LE>()?;
         self.internal_struct.read(reader)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    /// Verifies the byte layout of `FieldReference` against a captured reference.
+    fn test_field_reference_layout() {
+        let data = [
+            0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, // type_info
+            0x78, 0x56, 0x34, 0x12, // global_id
+            0xEF, 0xCD, 0xAB, 0x89, 0x67, 0x45, 0x23, 0x01, // asset_id
+            0x20, 0x74, 0x61, 0x6D, // group ("mat " reversed)
+            0xFF, 0xFF, 0xFF, 0xFF, // local_handle
+        ];
+        let mut reader = Cursor::new(&data[..]);
+        let mut reference = FieldReference::default();
+        reference.read(&mut reader).unwrap();
+
+        assert_eq!(reference.type_info(), 0x1122_3344_5566_7788);
+        assert_eq!(reference.global_id, 0x1234_5678);
+        assert_eq!(reference.asset_id, 0x0123_4567_89AB_CDEF);
+        assert_eq!(reference.group, "mat ");
+        assert_eq!(reference.local_handle(), -1);
+        assert_eq!(reader.position(), 0x1C);
+    }
+
+    #[test]
+    /// Verifies that trimmed strings stop at the first null byte while raw bytes keep all data.
+    fn test_field_string_trimmed_and_raw() {
+        let mut data = [0u8; 32];
+        data[..4].copy_from_slice(b"path");
+        data[5..9].copy_from_slice(b"data");
+        let mut string = FieldString::default();
+        string.read(&mut Cursor::new(&data[..])).unwrap();
+
+        assert_eq!(string.trimmed(), "path");
+        assert_eq!(string.raw_bytes(), &data[..]);
+        assert_eq!(string.0.len(), 32);
+    }
+
+    #[test]
+    /// Verifies that only non-zero string IDs are reported as set.
+    fn test_string_id_is_set() {
+        assert!(!FieldStringId::default().is_set());
+        assert!(FieldStringId(0x1234_5678).is_set());
+        assert!(FieldStringId(-1).is_set());
+    }
+
+    #[test]
+    /// Verifies that wide strings round-trip through their UTF-16 encoding, including non-ASCII characters.
+    fn test_field_wide_string() {
+        let text = "Spartan–117 ✓";
+        let mut data: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        data.resize(64, 0);
+        let mut reader = Cursor::new(&data[..]);
+        let mut string = FieldWideString::<32>::default();
+        string.read(&mut reader).unwrap();
+
+        assert_eq!(string.trimmed(), text);
+        assert_eq!(string.0.chars().count(), 32);
+        assert_eq!(reader.position(), 64);
+    }
+
+    #[test]
+    /// Verifies that angles are converted to degrees and wrapped around to [-π, π).
+    fn test_field_angle() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(close(FieldAngle(PI).degrees(), 180.0));
+        assert!(close(FieldAngle(-PI / 2.0).degrees(), -90.0));
+
+        for (radians, expected) in [
+            (0.0, 0.0),
+            (PI / 2.0, PI / 2.0),
+            (3.0 * PI / 2.0, -PI / 2.0),
+            (-3.0 * PI / 2.0, PI / 2.0),
+            (5.0 * PI / 2.0, PI / 2.0),
+            (-TAU, 0.0),
+        ] {
+            assert!(close(FieldAngle(radians).normalized(), expected));
+        }
+
+        let bounds = FieldAngleBounds {
+            min: -PI / 4.0,
+            max: 3.0 * PI / 2.0,
+        };
+        let (min, max) = bounds.degrees();
+        assert!(close(min, -45.0) && close(max, 270.0));
+        let (min, max) = bounds.normalized();
+        assert!(close(min, -PI / 4.0) && close(max, -PI / 2.0));
+    }
+
+    #[test]
+    /// Verifies that booleans are read from a single byte, treating any non-zero value as `true`.
+    fn test_field_bool() {
+        let mut reader = Cursor::new(&[0u8, 1, 0xFF][..]);
+        let mut field = FieldBool::default();
+        assert!(!field.0);
+
+        for expected in [false, true, true] {
+            field.read(&mut reader).unwrap();
+            assert_eq!(field.0, expected);
+        }
+        assert_eq!(reader.position(), 3);
+    }
+
+    #[test]
+    /// Verifies that `FieldData` consumes exactly `FieldData::SIZE` bytes.
+    fn test_field_data_size() {
+        let mut data = [0u8; 0x20];
+        data[0x14..0x18].copy_from_slice(&0x40u32.to_le_bytes());
+        let mut reader = Cursor::new(&data[..]);
+        let mut field = FieldData::default();
+        field.read(&mut reader).unwrap();
+
+        assert_eq!(field.size, 0x40);
+        assert_eq!(reader.position(), FieldData::SIZE);
+    }
+
+    #[test]
+    /// Verifies that coordinates are read as signed or unsigned depending on the type parameter.
+    fn test_point_signed_coordinates() {
+        let data = [0xFF, 0xFF, 0x02, 0x00];
+
+        let mut unsigned: FieldPoint2D = FieldPoint2D::default();
+        unsigned.read(&mut Cursor::new(&data[..])).unwrap();
+        assert_eq!((unsigned.x, unsigned.y), (0xFFFF, 2));
+
+        let mut signed = FieldPoint2D::<i16>::default();
+        signed.read(&mut Cursor::new(&data[..])).unwrap();
+        assert_eq!((signed.x, signed.y), (-1, 2));
+
+        let mut rectangle = FieldRectangle2D::<i16>::default();
+        rectangle.read(&mut Cursor::new(&data[..])).unwrap();
+        assert_eq!((rectangle.x, rectangle.y), (-1, 2));
+    }
+}
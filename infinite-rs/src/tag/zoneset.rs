@@ -0,0 +1,158 @@
+//! Zoneset section of the tag header, listing the tags that belong to each zoneset.
+//!
+//! The layout of the section follows earlier versions of this crate and has not been verified against game files. It is
+//! only parsed through [`TagFile::zonesets`](`crate::tag::loader::TagFile::zonesets`).
+
+use byteorder::{ReadBytesExt, LE};
+
+use crate::common::extensions::{BufReaderExt, Enumerable};
+use crate::Result;
+
+#[derive(Default, Debug)]
+/// Tag referenced by a zoneset.
+pub struct TagZonesetTag {
+    /// Global tag ID of the tag.
+    pub global_id: i32,
+    /// `MurmurHash3_x86_64` 32 bit hash of the name of the zoneset the tag is loaded in.
+    pub string_id: i32,
+}
+
+impl Enumerable for TagZonesetTag {
+    fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+        self.global_id = reader.read_i32::<LE>()?;
+        self.string_id = reader.read_i32::<LE>()?;
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug)]
+/// Header of a single zoneset, containing the counts of the lists that follow it.
+pub struct TagZonesetInstanceHeader {
+    /// `MurmurHash3_x86_64` 32 bit hash of the name of the zoneset.
+    pub string_id: i32,
+    /// Number of tags in [`tags`](`TagZonesetInstance::tags`).
+    pub tag_count: u32,
+    /// Number of parents in [`parents`](`TagZonesetInstance::parents`).
+    pub parent_count: u32,
+    /// Number of tags in [`footer_tags`](`TagZonesetInstance::footer_tags`).
+    pub footer_count: u32,
+}
+
+impl Enumerable for TagZonesetInstanceHeader {
+    fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+        self.string_id = reader.read_i32::<LE>()?;
+        self.tag_count = reader.read_u32::<LE>()?;
+        self.parent_count = reader.read_u32::<LE>()?;
+        self.footer_count = reader.read_u32::<LE>()?;
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug)]
+/// Zoneset along with the tags that belong to it.
+pub struct TagZonesetInstance {
+    /// Counts of the lists in the zoneset.
+    pub header: TagZonesetInstanceHeader,
+    /// Tags loaded with the zoneset.
+    pub tags: Vec<TagZonesetTag>,
+    /// Tags stored after the main list, unknown use.
+    pub footer_tags: Vec<TagZonesetTag>,
+    /// String IDs of the zonesets this zoneset inherits from.
+    pub parents: Vec<i32>,
+}
+
+impl Enumerable for TagZonesetInstance {
+    fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+        self.header.read(reader)?;
+        self.tags = reader.read_enumerable::<TagZonesetTag>(u64::from(self.header.tag_count))?;
+        self.footer_tags =
+            reader.read_enumerable::<TagZonesetTag>(u64::from(self.header.footer_count))?;
+        self.parents = (0..self.header.parent_count)
+            .map(|_| -> Result<i32> { Ok(reader.read_i32::<LE>()?) })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug)]
+/// Header of the zoneset section.
+pub struct TagZonesetHeader {
+    /// Version of the zoneset section.
+    pub version: i32,
+    /// Number of zonesets in the section.
+    pub zoneset_count: u32,
+    /// Total number of footer tags across all zonesets.
+    pub footer_count: u32,
+    /// Total number of parents across all zonesets.
+    pub parent_count: u32,
+}
+
+impl Enumerable for TagZonesetHeader {
+    fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+        self.version = reader.read_i32::<LE>()?;
+        self.zoneset_count = reader.read_u32::<LE>()?;
+        self.footer_count = reader.read_u32::<LE>()?;
+        self.parent_count = reader.read_u32::<LE>()?;
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug)]
+/// Zoneset section of a tag, stored after the string table in the tag header.
+///
+/// Zonesets group the tags that are loaded together, for instance with a BSP of a level.
+pub struct TagZoneset {
+    /// Header containing the number of zonesets.
+    pub header: TagZonesetHeader,
+    /// Zonesets in the section.
+    pub zonesets: Vec<TagZonesetInstance>,
+}
+
+impl Enumerable for TagZoneset {
+    fn read<R: BufReaderExt>(&mut self, reader: &mut R) -> Result<()> {
+        self.header.read(reader)?;
+        self.zonesets =
+            reader.read_enumerable::<TagZonesetInstance>(u64::from(self.header.zoneset_count))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::extensions::ByteReader;
+
+    #[test]
+    /// Verifies that the tags, footer tags and parents of each zoneset are read in order.
+    fn test_read_zoneset() {
+        let mut data = Vec::new();
+        for value in [1i32, 1, 1, 1] {
+            // version, zoneset count, footer count, parent count
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [0x10i32, 2, 1, 1] {
+            // string id, tag count, parent count, footer count
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [100i32, 0x10, 101, 0x10, 102, 0x20, 0x30] {
+            // tags, footer tags and parents
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let mut zoneset = TagZoneset::default();
+        zoneset.read(&mut ByteReader::new(&data[..])).unwrap();
+        assert_eq!(zoneset.header.zoneset_count, 1);
+        let instance = &zoneset.zonesets[0];
+        assert_eq!(instance.header.string_id, 0x10);
+        assert_eq!(
+            instance
+                .tags
+                .iter()
+                .map(|tag| tag.global_id)
+                .collect::<Vec<_>>(),
+            [100, 101]
+        );
+        assert_eq!(instance.footer_tags[0].global_id, 102);
+        assert_eq!(instance.parents, [0x30]);
+    }
+}
@@ -0,0 +1,418 @@
+//! Tests for the full module loading path, using a minimal module built in memory.
+
+use infinite_rs::common::errors::{ModuleError, TagError};
+use infinite_rs::module::file::DataOffsetType;
+use infinite_rs::tag::datablock::TagSectionType;
+use infinite_rs::tag::types::common_types::AnyTag;
+use infinite_rs::{Error, ModuleFile, TagId};
+use infinite_rs_derive::TagStructure;
+
+/// Magic of the module header ("mohd").
+const MODULE_MAGIC: u32 = 0x6468_6F6D;
+/// Magic of the tag header ("ucsh").
+const TAG_MAGIC: u32 = 0x6873_6375;
+/// Data of the files in the module starts at the next multiple of 0x1000 after the tables.
+const FILE_DATA_OFFSET: usize = 0x1000;
+
+/// Builds a tag containing a single main struct, stored in a datablock of `data.len()` bytes.
+fn synthetic_tag(data: &[u8]) -> Vec<u8> {
+    let data_size = u32::try_from(data.len()).unwrap();
+    let mut tag = Vec::new();
+    // Header
+    tag.extend_from_slice(&TAG_MAGIC.to_le_bytes());
+    tag.extend_from_slice(&27i32.to_le_bytes()); // version
+    tag.extend_from_slice(&[0; 16]); // root struct guid and checksum
+    for count in [0u32, 1, 1, 0, 0] {
+        // dependencies, datablocks, structs, data references, tag references
+        tag.extend_from_slice(&count.to_le_bytes());
+    }
+    tag.extend_from_slice(&[0; 12]); // string table size, zoneset size, unknown
+    for size in [0x80, data_size, 0, 0] {
+        // header, data, resource and actual resource sizes
+        tag.extend_from_slice(&size.to_le_bytes());
+    }
+    tag.extend_from_slice(&[0; 8]); // alignments and resource flag
+
+    // Datablock
+    tag.extend_from_slice(&data_size.to_le_bytes());
+    tag.extend_from_slice(&0u16.to_le_bytes()); // padding
+    tag.extend_from_slice(&(TagSectionType::TagData as u16).to_le_bytes());
+    tag.extend_from_slice(&0u64.to_le_bytes()); // offset
+
+    // Main struct
+    tag.extend_from_slice(&[0; 16]); // guid
+    tag.extend_from_slice(&0u16.to_le_bytes()); // main struct type
+    tag.extend_from_slice(&0u16.to_le_bytes()); // unknown
+    tag.extend_from_slice(&0i32.to_le_bytes()); // target index
+    tag.extend_from_slice(&(-1i32).to_le_bytes()); // field block
+    tag.extend_from_slice(&0u32.to_le_bytes()); // field offset
+    assert_eq!(tag.len(), 0x80);
+    tag.extend_from_slice(data);
+    tag
+}
+
+/// Builds a Season 3 module containing a single uncompressed file stored in one block.
+fn synthetic_module(tag_id: i32, tag: &[u8]) -> Vec<u8> {
+    let tag_size = u32::try_from(tag.len()).unwrap();
+    let mut module = Vec::new();
+    // Header
+    module.extend_from_slice(&MODULE_MAGIC.to_le_bytes());
+    module.extend_from_slice(&53i32.to_le_bytes()); // version
+    module.extend_from_slice(&0i64.to_le_bytes()); // module id
+    module.extend_from_slice(&1u32.to_le_bytes()); // file count
+    for index in [-1i32, -1, -1, 1] {
+        // loadmanifest, runtimeloadmetadata, resourcemetadata and resource indices
+        module.extend_from_slice(&index.to_le_bytes());
+    }
+    module.extend_from_slice(&0u32.to_le_bytes()); // strings size
+    module.extend_from_slice(&0u32.to_le_bytes()); // resource count
+    module.extend_from_slice(&1u32.to_le_bytes()); // block count
+    module.extend_from_slice(&[0; 24]); // build version, HD1 delta, data size
+    module.extend_from_slice(&[0; 8]);
+
+    // File entry
+    module.push(0); // unknown
+    module.push(0b10); // has blocks
+    module.extend_from_slice(&1u16.to_le_bytes()); // block count
+    module.extend_from_slice(&0i32.to_le_bytes()); // block index
+    module.extend_from_slice(&(-1i32).to_le_bytes()); // resource index
+    module.extend_from_slice(b" tam"); // tag group, reversed
+    module.extend_from_slice(&0u64.to_le_bytes()); // data offset
+    module.extend_from_slice(&tag_size.to_le_bytes()); // compressed size
+    module.extend_from_slice(&tag_size.to_le_bytes()); // uncompressed size
+    module.extend_from_slice(&tag_id.to_le_bytes());
+    for size in [0x80, tag_size - 0x80, 0, 0] {
+        // header, tag data, resource data and actual resource sizes
+        module.extend_from_slice(&size.to_le_bytes());
+    }
+    module.extend_from_slice(&[0; 4]); // alignments
+    module.extend_from_slice(&0u32.to_le_bytes()); // name offset
+    module.extend_from_slice(&(-1i32).to_le_bytes()); // parent index
+    module.extend_from_slice(&[0; 16]); // asset hash
+    module.extend_from_slice(&0i32.to_le_bytes()); // resource count
+    module.extend_from_slice(&[0; 4]); // padding
+
+    // Block
+    for value in [0, tag_size, 0, tag_size, 0] {
+        // compressed offset and size, decompressed offset and size, is compressed
+        module.extend_from_slice(&value.to_le_bytes());
+    }
+    module.resize(FILE_DATA_OFFSET, 0);
+    module.extend_from_slice(tag);
+    module
+}
+
+#[test]
+/// Verifies that a synthetic module is parsed and that its tag is read back unchanged.
+fn test_read_synthetic_module() {
+    let tag = synthetic_tag(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let mut module = ModuleFile::default();
+    module.read_from_slice(synthetic_module(42, &tag)).unwrap();
+
+    assert_eq!(module.files.len(), 1);
+    assert_eq!(module.files[0].tag_group, "mat ");
+    assert_eq!(module.files[0].tag_id, 42);
+
+    let file = module.read_tag(0).unwrap().unwrap();
+    let data = file.data_stream.as_ref().unwrap().get_ref().get_ref();
+    assert_eq!(data, &tag);
+
+    let tag_info = file.tag_info.as_ref().unwrap();
+    assert_eq!(tag_info.datablock_definitions.len(), 1);
+    assert_eq!(tag_info.main_struct().unwrap().target_index, 0);
+    assert_eq!(
+        file.split_sections().unwrap().tag_data,
+        [1, 2, 3, 4, 5, 6, 7, 8]
+    );
+}
+
+#[test]
+/// Verifies that file indices and block ranges outside of the module return errors instead of panicking.
+fn test_read_tag_invalid_index() {
+    let tag = synthetic_tag(&[0; 8]);
+    let mut module = ModuleFile::default();
+    module.read_from_slice(synthetic_module(42, &tag)).unwrap();
+    assert!(matches!(
+        module.read_tag(1),
+        Err(Error::ModuleError(ModuleError::InvalidIndex))
+    ));
+
+    let mut data = synthetic_module(42, &tag);
+    data[0x54..0x58].copy_from_slice(&1i32.to_le_bytes()); // block index, past the only block
+    let mut module = ModuleFile::default();
+    module.read_from_slice(data).unwrap();
+    assert!(matches!(
+        module.read_tag(0),
+        Err(Error::ModuleError(ModuleError::InvalidIndex))
+    ));
+}
+
+#[test]
+/// Verifies that the block table of a module is exposed along with the range of blocks used by each file.
+fn test_blocks() {
+    let tag = synthetic_tag(&[0; 8]);
+    let size = u32::try_from(tag.len()).unwrap();
+    let mut module = ModuleFile::default();
+    module.read_from_slice(synthetic_module(1, &tag)).unwrap();
+
+    let blocks = module.blocks();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].compressed_offset(), 0);
+    assert_eq!(blocks[0].compressed_size(), size);
+    assert_eq!(blocks[0].decompressed_offset(), 0);
+    assert_eq!(blocks[0].decompressed_size(), size);
+    assert!(!blocks[0].is_compressed());
+    assert_eq!(module.files[0].block_index(), 0);
+    assert_eq!(module.files[0].block_count(), 1);
+}
+
+#[test]
+/// Verifies that tags are found by their [`TagId`], and that unknown IDs return nothing.
+fn test_read_tag_from_id() {
+    let tag = synthetic_tag(&[0; 8]);
+    let mut module = ModuleFile::default();
+    module.read_from_slice(synthetic_module(42, &tag)).unwrap();
+
+    let file = module.read_tag_from_id(TagId(42)).unwrap().unwrap();
+    assert!(file.is_loaded);
+    assert!(module.read_tag_from_id(TagId::from(43)).unwrap().is_none());
+}
+
+#[test]
+/// Verifies that the load state of a file is only reported once it is read.
+fn test_load_state() {
+    let mut module = ModuleFile::default();
+    module
+        .read_from_slice(synthetic_module(42, &synthetic_tag(&[0; 8])))
+        .unwrap();
+    assert!(!module.files[0].is_loaded());
+    assert!(!module.files[0].has_metadata());
+
+    let file = module.read_tag(0).unwrap().unwrap();
+    assert!(file.is_loaded());
+    assert!(file.has_metadata());
+}
+
+/// Builds the tag data of a tag whose main struct only contains an `AnyTag` with the given ID.
+fn any_tag_data(tag_id: i32) -> Vec<u8> {
+    let mut data = vec![0; 8]; // vtable space
+    data.extend_from_slice(&tag_id.to_le_bytes());
+    data.extend_from_slice(&(-1i32).to_le_bytes()); // local tag handle
+    data
+}
+
+#[test]
+/// Verifies that the `AnyTag` of each tag matches its file entry, and that mismatches are reported.
+fn test_verify_any_tags() {
+    let mut module = ModuleFile::default();
+    module
+        .read_from_slice(synthetic_module(42, &synthetic_tag(&any_tag_data(42))))
+        .unwrap();
+    assert!(module
+        .read_tag(0)
+        .unwrap()
+        .unwrap()
+        .verify_any_tag()
+        .unwrap());
+    assert!(module.verify_any_tags().unwrap().is_empty());
+
+    let mut module = ModuleFile::default();
+    module
+        .read_from_slice(synthetic_module(42, &synthetic_tag(&any_tag_data(43))))
+        .unwrap();
+    assert_eq!(module.verify_any_tags().unwrap(), [0]);
+}
+
+#[derive(Default, Debug, TagStructure)]
+#[data(size(0x10))]
+/// Main struct of the tags built with [`any_tag_data`].
+struct AnyTagStruct {
+    #[data(offset(0x00))]
+    any_tag: AnyTag,
+}
+
+#[test]
+/// Verifies that a tag is loaded and its metadata read in a single call, and that reading the metadata of a tag that
+/// is not loaded yet is reported.
+fn test_read_full() {
+    let mut module = ModuleFile::default();
+    module
+        .read_from_slice(synthetic_module(42, &synthetic_tag(&any_tag_data(42))))
+        .unwrap();
+    assert!(matches!(
+        module.files[0].read_metadata(&mut AnyTagStruct::default()),
+        Err(Error::TagError(TagError::NotLoaded))
+    ));
+
+    let tag = module.read_full::<AnyTagStruct>(0).unwrap().unwrap();
+    assert_eq!(tag.any_tag.internal_struct.tag_id, 42);
+    assert!(module.files[0].is_loaded());
+}
+
+#[test]
+/// Verifies that tags released by `iter_metadata` are reported as not loaded, so that they can be read again.
+fn test_iter_metadata_reload() {
+    let mut module = ModuleFile::default();
+    module
+        .read_from_slice(synthetic_module(42, &synthetic_tag(&any_tag_data(42))))
+        .unwrap();
+
+    let tags = module
+        .iter_metadata::<AnyTagStruct>("mat ")
+        .collect::<infinite_rs::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].1.any_tag.internal_struct.tag_id, 42);
+    assert!(!module.files[0].is_loaded());
+
+    let file = module.read_tag(0).unwrap().unwrap();
+    let mut tag = AnyTagStruct::default();
+    file.read_metadata(&mut tag).unwrap();
+    assert_eq!(tag.any_tag.internal_struct.tag_id, 42);
+}
+
+#[test]
+/// Verifies that a cleared module can be used to read another module, keeping the capacity of its tables.
+fn test_clear_and_reuse() {
+    let mut module = ModuleFile::default();
+    module
+        .read_from_slice(synthetic_module(42, &synthetic_tag(&[1, 2, 3, 4])))
+        .unwrap();
+    module.read_tag(0).unwrap();
+    let capacity = module.files.capacity();
+
+    module.clear();
+    assert!(module.files.is_empty());
+    assert!(module.blocks().is_empty());
+    assert_eq!(module.header.file_count, 0);
+    assert_eq!(module.files.capacity(), capacity);
+
+    let tag = synthetic_tag(&[5, 6, 7, 8, 9, 10, 11, 12]);
+    module.read_from_slice(synthetic_module(43, &tag)).unwrap();
+    assert_eq!(module.files.len(), 1);
+    assert_eq!(module.blocks().len(), 1);
+    assert_eq!(module.files[0].tag_id, 43);
+
+    let file = module.read_tag(0).unwrap().unwrap();
+    assert_eq!(file.data_stream.as_ref().unwrap().get_ref().get_ref(), &tag);
+}
+
+#[test]
+/// Verifies that the parsed tag file is returned directly, and that debug files return nothing.
+fn test_read_tag_info() {
+    let tag = synthetic_tag(&[0; 8]);
+    let mut module = ModuleFile::default();
+    module.read_from_slice(synthetic_module(42, &tag)).unwrap();
+
+    let tag_info = module.read_tag_info(0).unwrap().unwrap();
+    assert_eq!(tag_info.datablock_definitions.len(), 1);
+    assert_eq!(tag_info.main_struct().unwrap().target_index, 0);
+
+    module.files[0].data_offset_flags = DataOffsetType::DEBUG;
+    assert!(module.read_tag_info(0).unwrap().is_none());
+}
+
+#[test]
+#[cfg(feature = "walkdir")]
+/// Verifies that the module containing a tag is found by reading only the file tables of a deploy folder.
+fn test_locate_tag() {
+    use infinite_rs::module::set::ModuleSet;
+    use infinite_rs::FileIndex;
+    use std::{env, fs, process};
+
+    let deploy = env::temp_dir().join(format!("infinite-rs-deploy-{}", process::id()));
+    fs::create_dir_all(deploy.join("any")).unwrap();
+    let tag = synthetic_tag(&[0; 8]);
+    fs::write(deploy.join("a.module"), synthetic_module(1, &tag)).unwrap();
+    fs::write(deploy.join("any/b.module"), synthetic_module(2, &tag)).unwrap();
+    fs::write(deploy.join("any/b.txt"), [0; 4]).unwrap();
+
+    assert_eq!(
+        ModuleSet::locate_tag(&[&deploy], TagId(2)).unwrap(),
+        Some((deploy.join("any/b.module"), FileIndex(0)))
+    );
+    assert_eq!(ModuleSet::locate_tag(&[&deploy], TagId(3)).unwrap(), None);
+    fs::remove_dir_all(&deploy).unwrap();
+}
+
+#[test]
+/// Verifies that the entry table contains one record per file with the values of the file table.
+fn test_entry_table() {
+    let tag = synthetic_tag(&[0; 8]);
+    let size = u32::try_from(tag.len()).unwrap();
+    let mut module = ModuleFile::default();
+    module.read_from_slice(synthetic_module(42, &tag)).unwrap();
+
+    let table = module.entry_table();
+    assert_eq!(table.len(), module.header.file_count as usize);
+    assert_eq!(table[0].tag_id, 42);
+    assert_eq!(table[0].tag_group, "mat ");
+    assert_eq!(table[0].flags, 0b10);
+    assert_eq!(table[0].block_count, 1);
+    assert_eq!(table[0].total_uncompressed_size, size);
+    assert_eq!(table[0].section_sizes, [0x80, size - 0x80, 0, 0]);
+    assert_eq!(table[0].parent_index, -1);
+}
+
+#[test]
+/// Verifies that two modules read their tags from a single shared HD1 handle, both in place and as owned tags.
+fn test_shared_hd1() {
+    use infinite_rs::module::builder::ModuleFileBuilder;
+    use std::fs::{self, File};
+    use std::io::BufReader;
+    use std::sync::{Arc, Mutex};
+    use std::{env, process};
+
+    let directory = env::temp_dir().join(format!("infinite-rs-hd1-{}", process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let tags = [synthetic_tag(&[1; 8]), synthetic_tag(&[2; 8])];
+
+    // Both tags are stored in the HD1 file, one after the other.
+    let mut hd1 = vec![0; 0x10];
+    for (index, tag) in tags.iter().enumerate() {
+        let mut module = synthetic_module(i32::try_from(index).unwrap(), tag);
+        module[0x38..0x40].copy_from_slice(&(hd1.len() as u64).to_le_bytes()); // HD1 delta
+        module[0x60..0x68].copy_from_slice(&(1u64 << 48).to_le_bytes()); // data offset, stored in HD1
+        fs::write(directory.join(format!("{index}.module")), module).unwrap();
+        hd1.extend_from_slice(tag);
+    }
+    let hd1_path = directory.join("shared.module_hd1");
+    fs::write(&hd1_path, hd1).unwrap();
+
+    let hd1_file = Arc::new(Mutex::new(BufReader::new(File::open(&hd1_path).unwrap())));
+    for (index, tag) in tags.iter().enumerate() {
+        let mut module = ModuleFileBuilder::new()
+            .shared_hd1(Arc::clone(&hd1_file))
+            .open(directory.join(format!("{index}.module")))
+            .unwrap();
+        assert!(module.use_hd1);
+        assert_eq!(module.count_hd1_entries(), 1);
+
+        let loaded = module.read_tag_owned(0).unwrap().unwrap();
+        assert_eq!(loaded.data_stream.get_ref().get_ref(), tag);
+
+        let file = module.read_tag(0).unwrap().unwrap();
+        assert_eq!(file.data_stream.as_ref().unwrap().get_ref().get_ref(), tag);
+    }
+    assert_eq!(Arc::strong_count(&hd1_file), 1);
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+/// Verifies that reading every tag continues past tags that fail to be read.
+fn test_read_all_tags_lenient() {
+    use infinite_rs::module::file::ModuleFileEntry;
+
+    let mut tag = synthetic_tag(&[0; 8]);
+    tag[0] = 0; // Corrupt the magic of the tag header.
+    let mut module = ModuleFile::default();
+    module.read_from_slice(synthetic_module(42, &tag)).unwrap();
+    let mut debug_entry = ModuleFileEntry::default();
+    debug_entry.data_offset_flags = DataOffsetType::DEBUG;
+    module.files.push(debug_entry);
+
+    let results = module.read_all_tags_lenient();
+    assert_eq!(results.len(), 2);
+    assert!(matches!(results[0], (0, Err(_))));
+    assert!(matches!(results[1], (1, Ok(()))));
+}